@@ -73,7 +73,8 @@ DEPLOY_OUTPUT=$(stellar contract deploy \
   --source "$SOURCE_ACCOUNT" \
   --network "$NETWORK_NAME" \
   -- \
-  --vk_bytes-file-path "$DATASET_DIR/vk")
+  --vk_bytes-file-path "$DATASET_DIR/vk" \
+  --oracle_hash "${ORACLE_HASH:-0}")
 echo "$DEPLOY_OUTPUT"
 CONTRACT_ID=$(echo "$DEPLOY_OUTPUT" | tail -n 1 | tr -d '[:space:]')
 if [[ -z "$CONTRACT_ID" ]]; then
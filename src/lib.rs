@@ -1,6 +1,33 @@
 #![no_std]
-use soroban_sdk::{contract, contracterror, contractimpl, symbol_short, Bytes, Env, Symbol};
-use ultrahonk_soroban_verifier::{UltraHonkVerifier, PROOF_BYTES};
+extern crate alloc;
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Bytes, BytesN, Env, IntoVal,
+    Symbol, Val, Vec,
+};
+use ultrahonk_soroban_verifier::{
+    ec::MsmBackend,
+    types::Transcript,
+    utils::{load_transcript, transcript_to_bytes},
+    verifier::VerifyError,
+    ParseError, UltraHonkVerifier,
+};
+
+// Staged verification progress per proof hash, kept in temporary storage
+// until stage 3 succeeds.
+const STAGE_TRANSCRIPT: u32 = 1;
+const STAGE_SUMCHECK: u32 = 2;
+
+// TTLs in ledgers (~5 s each): a staged verification must finish within
+// about a day of its last stage; a verified flag stays live about 30 days
+// from when it was last written.
+const STAGE_TTL_LEDGERS: u32 = 17_280;
+const VERIFIED_TTL_LEDGERS: u32 = 518_400;
+
+#[cfg(not(feature = "msm-per-term"))]
+const MSM_BACKEND: MsmBackend = MsmBackend::Windowed;
+#[cfg(feature = "msm-per-term")]
+const MSM_BACKEND: MsmBackend = MsmBackend::PerTerm;
 
 /// Contract
 #[contract]
@@ -14,6 +41,45 @@ pub enum Error {
     ProofParseError = 2,
     VerificationFailed = 3,
     VkNotSet = 4,
+    NonCanonicalEncoding = 5,
+    BadHeader = 6,
+    PointNotOnCurve = 7,
+    PointAtInfinity = 8,
+    InvalidVk = 9,
+    StageOutOfOrder = 10,
+}
+
+impl Error {
+    /// Map a decoding failure; `wrong_length` says which input was sized wrong.
+    fn from_parse(e: ParseError, wrong_length: Error) -> Error {
+        match e {
+            ParseError::WrongLength | ParseError::IrregularPadding => wrong_length,
+            ParseError::BadHeader => Error::BadHeader,
+            ParseError::PointNotOnCurve => Error::PointNotOnCurve,
+            ParseError::PointAtInfinity => Error::PointAtInfinity,
+            ParseError::NonCanonicalScalar | ParseError::NonCanonicalCoordinate => {
+                Error::NonCanonicalEncoding
+            }
+        }
+    }
+}
+
+/// Transcript hash the proofs were generated with (`bb prove --oracle_hash`).
+#[contracttype]
+#[repr(u32)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum OracleHash {
+    Keccak = 0,
+    Poseidon2 = 1,
+}
+
+impl From<OracleHash> for ultrahonk_soroban_verifier::OracleHash {
+    fn from(h: OracleHash) -> Self {
+        match h {
+            OracleHash::Keccak => Self::Keccak,
+            OracleHash::Poseidon2 => Self::Poseidon2,
+        }
+    }
 }
 
 #[contractimpl]
@@ -22,30 +88,230 @@ impl UltraHonkVerifierContract {
         symbol_short!("vk")
     }
 
-    /// Initialize the on-chain VK once at deploy time.
-    pub fn __constructor(env: Env, vk_bytes: Bytes) -> Result<(), Error> {
+    fn key_oracle() -> Symbol {
+        symbol_short!("oracle")
+    }
+
+    fn load_verifier(env: &Env, vk_bytes: &Bytes) -> Result<UltraHonkVerifier, Error> {
+        UltraHonkVerifier::new(env, vk_bytes).map_err(|e| match e {
+            VerifyError::Parse(e) => Error::from_parse(e, Error::VkParseError),
+            VerifyError::InvalidVk(_) => Error::InvalidVk,
+            _ => Error::VkParseError,
+        })
+    }
+
+    /// Initialize the on-chain VK and transcript hash once at deploy time.
+    /// The VK is parsed and its metadata validated here, so a bad VK fails
+    /// the deployment rather than the first verification.
+    pub fn __constructor(env: Env, vk_bytes: Bytes, oracle_hash: OracleHash) -> Result<(), Error> {
+        Self::load_verifier(&env, &vk_bytes)?;
         env.storage().instance().set(&Self::key_vk(), &vk_bytes);
+        env.storage()
+            .instance()
+            .set(&Self::key_oracle(), &oracle_hash);
         Ok(())
     }
 
-    /// Verify an UltraHonk proof using the stored VK.
-    pub fn verify_proof(env: Env, public_inputs: Bytes, proof_bytes: Bytes) -> Result<(), Error> {
-        if proof_bytes.len() as usize != PROOF_BYTES {
-            return Err(Error::ProofParseError);
-        }
+    fn key_stage() -> Symbol {
+        symbol_short!("stage")
+    }
 
+    fn key_transcript() -> Symbol {
+        symbol_short!("tx")
+    }
+
+    fn stored_verifier(env: &Env) -> Result<UltraHonkVerifier, Error> {
         let vk_bytes: Bytes = env
             .storage()
             .instance()
             .get(&Self::key_vk())
             .ok_or(Error::VkNotSet)?;
+        let oracle_hash: OracleHash = env
+            .storage()
+            .instance()
+            .get(&Self::key_oracle())
+            .unwrap_or(OracleHash::Keccak);
         // Deserialize verification key bytes
-        let verifier = UltraHonkVerifier::new(&env, &vk_bytes).map_err(|_| Error::VkParseError)?;
+        Ok(Self::load_verifier(env, &vk_bytes)?
+            .with_oracle_hash(oracle_hash.into())
+            .with_msm_backend(MSM_BACKEND))
+    }
+
+    fn verify_error(e: VerifyError) -> Error {
+        match e {
+            VerifyError::Parse(e) => Error::from_parse(e, Error::ProofParseError),
+            _ => Error::VerificationFailed,
+        }
+    }
+
+    /// Keccak-256 of `len(proof_bytes) ‖ proof_bytes ‖ public_inputs`, the
+    /// length as a big-endian u32, so no two (proof, public inputs) splits
+    /// of the same bytes share a hash. Identifies a staged verification and
+    /// is the key `is_verified` takes.
+    pub fn proof_hash(env: Env, public_inputs: Bytes, proof_bytes: Bytes) -> BytesN<32> {
+        let mut data = Bytes::from_array(&env, &proof_bytes.len().to_be_bytes());
+        data.append(&proof_bytes);
+        data.append(&public_inputs);
+        env.crypto().keccak256(&data).into()
+    }
+
+    fn key_verified() -> Symbol {
+        symbol_short!("verified")
+    }
+
+    /// Store per-proof stage state in temporary storage, live for another
+    /// `STAGE_TTL_LEDGERS`.
+    fn set_stage_entry<V: IntoVal<Env, Val>>(env: &Env, key: &(Symbol, BytesN<32>), value: &V) {
+        let temporary = env.storage().temporary();
+        temporary.set(key, value);
+        temporary.extend_ttl(key, STAGE_TTL_LEDGERS, STAGE_TTL_LEDGERS);
+    }
+
+    /// Advance the staged verification of `hash` from `from` to `from + 1`.
+    fn advance_stage(env: &Env, hash: &BytesN<32>, from: u32) -> Result<(), Error> {
+        let key = (Self::key_stage(), hash.clone());
+        let stage: u32 = env.storage().temporary().get(&key).unwrap_or(0);
+        if stage != from {
+            return Err(Error::StageOutOfOrder);
+        }
+        Self::set_stage_entry(env, &key, &(from + 1));
+        // Written alongside the stage marker, so it is still live
+        env.storage().temporary().extend_ttl(
+            &(Self::key_transcript(), hash.clone()),
+            STAGE_TTL_LEDGERS,
+            STAGE_TTL_LEDGERS,
+        );
+        Ok(())
+    }
+
+    fn staged_transcript(env: &Env, hash: &BytesN<32>) -> Result<Transcript, Error> {
+        let bytes: Bytes = env
+            .storage()
+            .temporary()
+            .get(&(Self::key_transcript(), hash.clone()))
+            .ok_or(Error::StageOutOfOrder)?;
+        load_transcript(&bytes).map_err(|_| Error::StageOutOfOrder)
+    }
+
+    /// Drop the stage markers and transcript of `hash` and record it as
+    /// verified in persistent storage.
+    fn finish_stages(env: &Env, hash: &BytesN<32>) {
+        let temporary = env.storage().temporary();
+        temporary.remove(&(Self::key_stage(), hash.clone()));
+        temporary.remove(&(Self::key_transcript(), hash.clone()));
+        let key = (Self::key_verified(), hash.clone());
+        env.storage().persistent().set(&key, &true);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, VERIFIED_TTL_LEDGERS, VERIFIED_TTL_LEDGERS);
+    }
+
+    /// Verify an UltraHonk proof (plain or ZK, any supported bb layout) using the stored VK.
+    pub fn verify_proof(env: Env, public_inputs: Bytes, proof_bytes: Bytes) -> Result<(), Error> {
+        let verifier = Self::stored_verifier(&env)?;
+        // Proof layout must match a supported bb serialization for this VK
+        if verifier.detect_proof_format(&proof_bytes).is_none() {
+            return Err(Error::ProofParseError);
+        }
 
         // Verify
         verifier
             .verify(&proof_bytes, &public_inputs)
-            .map_err(|_| Error::VerificationFailed)?;
+            .map_err(Self::verify_error)?;
         Ok(())
     }
+
+    /// Verify a plain proof sent in the compact encoding
+    /// (`ultrahonk_soroban_verifier::compact`) using the stored VK.
+    pub fn verify_compact_proof(
+        env: Env,
+        public_inputs: Bytes,
+        proof_bytes: Bytes,
+    ) -> Result<(), Error> {
+        Self::stored_verifier(&env)?
+            .verify_compact(&proof_bytes, &public_inputs)
+            .map_err(Self::verify_error)
+    }
+
+    /// Verify several proofs against the stored VK, settled with a single
+    /// pairing. Each entry is `(public_inputs, proof_bytes)` as for `verify_proof`.
+    pub fn verify_proofs(env: Env, proofs: Vec<(Bytes, Bytes)>) -> Result<(), Error> {
+        let verifier = Self::stored_verifier(&env)?;
+        let batch: alloc::vec::Vec<(Bytes, Bytes)> = proofs
+            .iter()
+            .map(|(public_inputs, proof_bytes)| (proof_bytes, public_inputs))
+            .collect();
+        verifier.verify_batch(&batch).map_err(Self::verify_error)
+    }
+
+    /// Staged verification, stage 1 of 3: derive the Fiat–Shamir transcript
+    /// and persist it under the proof hash, which is returned. Stages 2 and 3
+    /// take the same `public_inputs` and `proof_bytes` and are bound to it.
+    pub fn verify_stage_transcript(
+        env: Env,
+        public_inputs: Bytes,
+        proof_bytes: Bytes,
+    ) -> Result<BytesN<32>, Error> {
+        let hash = Self::proof_hash(env.clone(), public_inputs.clone(), proof_bytes.clone());
+        if Self::is_verified(env.clone(), hash.clone()) {
+            return Ok(hash);
+        }
+        let t = Self::stored_verifier(&env)?
+            .stage_transcript(&proof_bytes, &public_inputs)
+            .map_err(Self::verify_error)?;
+        Self::set_stage_entry(
+            &env,
+            &(Self::key_transcript(), hash.clone()),
+            &transcript_to_bytes(&env, &t),
+        );
+        Self::set_stage_entry(&env, &(Self::key_stage(), hash.clone()), &STAGE_TRANSCRIPT);
+        Ok(hash)
+    }
+
+    /// Staged verification, stage 2 of 3: sum-check.
+    pub fn verify_stage_sumcheck(
+        env: Env,
+        public_inputs: Bytes,
+        proof_bytes: Bytes,
+    ) -> Result<(), Error> {
+        let hash = Self::proof_hash(env.clone(), public_inputs.clone(), proof_bytes.clone());
+        Self::advance_stage(&env, &hash, STAGE_TRANSCRIPT)?;
+        let t = Self::staged_transcript(&env, &hash)?;
+        Self::stored_verifier(&env)?
+            .stage_sumcheck(&proof_bytes, &t)
+            .map_err(Self::verify_error)
+    }
+
+    /// Staged verification, stage 3 of 3: Shplemini and the final pairing.
+    /// On success the stage state is dropped and the proof hash is recorded
+    /// as verified.
+    pub fn verify_stage_final(
+        env: Env,
+        public_inputs: Bytes,
+        proof_bytes: Bytes,
+    ) -> Result<(), Error> {
+        let hash = Self::proof_hash(env.clone(), public_inputs.clone(), proof_bytes.clone());
+        let stage: u32 = env
+            .storage()
+            .temporary()
+            .get(&(Self::key_stage(), hash.clone()))
+            .unwrap_or(0);
+        if stage != STAGE_SUMCHECK {
+            return Err(Error::StageOutOfOrder);
+        }
+        let t = Self::staged_transcript(&env, &hash)?;
+        Self::stored_verifier(&env)?
+            .stage_shplemini(&proof_bytes, &t)
+            .map_err(Self::verify_error)?;
+        Self::finish_stages(&env, &hash);
+        Ok(())
+    }
+
+    /// Whether all three stages succeeded for `proof_hash` (within the last
+    /// `VERIFIED_TTL_LEDGERS` ledgers, unless the entry's TTL was extended).
+    pub fn is_verified(env: Env, proof_hash: BytesN<32>) -> bool {
+        env.storage()
+            .persistent()
+            .has(&(Self::key_verified(), proof_hash))
+    }
 }
@@ -68,11 +68,29 @@ for dir in tests/* ; do
   bb write_vk -b "$json" -o target \
     --scheme ultra_honk --oracle_hash keccak --output_format bytes_and_fields
 
-  if [[ -d target/vk && -f target/vk/vk ]]; then
-    mv target/vk/vk target/vk.tmp
-    rmdir target/vk
-    mv target/vk.tmp target/vk
-  fi
+  # ZK flavor (UltraKeccakZKFlavor) artifacts
+  mkdir -p target/zk
+  bb prove -b "$json" -w "$gz" -o target/zk \
+    --scheme ultra_honk --oracle_hash keccak --zk --output_format bytes_and_fields
+
+  bb write_vk -b "$json" -o target/zk \
+    --scheme ultra_honk --oracle_hash keccak --zk --output_format bytes_and_fields
+
+  # Poseidon2 oracle-hash (UltraFlavor) artifacts
+  mkdir -p target/poseidon2
+  bb prove -b "$json" -w "$gz" -o target/poseidon2 \
+    --scheme ultra_honk --oracle_hash poseidon2 --output_format bytes_and_fields
+
+  bb write_vk -b "$json" -o target/poseidon2 \
+    --scheme ultra_honk --oracle_hash poseidon2 --output_format bytes_and_fields
+
+  for out in target target/zk target/poseidon2; do
+    if [[ -d $out/vk && -f $out/vk/vk ]]; then
+      mv $out/vk/vk $out/vk.tmp
+      rmdir $out/vk
+      mv $out/vk.tmp $out/vk
+    fi
+  done
 
   popd >/dev/null
 done
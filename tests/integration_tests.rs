@@ -1,15 +1,29 @@
-use soroban_sdk::{Bytes, Env};
-use ultrahonk_soroban_verifier::PROOF_BYTES;
+use soroban_sdk::{testutils::Ledger, vec, Bytes, Env};
+use ultrahonk_soroban_verifier::{
+    compact::compact_proof, utils::load_vk_from_bytes, PROOF_BYTES, ZK_PROOF_BYTES,
+};
 
 const CONTRACT_WASM: &[u8] =
     include_bytes!("../target/wasm32v1-none/release/rs_soroban_ultrahonk.wasm");
+const CONTRACT_WASM_MSM_PER_TERM: &[u8] =
+    include_bytes!("../target/msm-per-term/rs_soroban_ultrahonk.wasm");
+const CONTRACT_WASM_SUMCHECK_BASELINE: &[u8] =
+    include_bytes!("../target/sumcheck-baseline/rs_soroban_ultrahonk.wasm");
 
 mod ultrahonk_contract {
     soroban_sdk::contractimport!(file = "target/wasm32v1-none/release/rs_soroban_ultrahonk.wasm");
 }
 
 fn register_client<'a>(env: &'a Env, vk_bytes: &Bytes) -> ultrahonk_contract::Client<'a> {
-    let contract_id = env.register(CONTRACT_WASM, (vk_bytes.clone(),));
+    register_client_with(env, vk_bytes, ultrahonk_contract::OracleHash::Keccak)
+}
+
+fn register_client_with<'a>(
+    env: &'a Env,
+    vk_bytes: &Bytes,
+    oracle_hash: ultrahonk_contract::OracleHash,
+) -> ultrahonk_contract::Client<'a> {
+    let contract_id = env.register(CONTRACT_WASM, (vk_bytes.clone(), oracle_hash));
     ultrahonk_contract::Client::new(env, &contract_id)
 }
 
@@ -51,6 +65,233 @@ fn verify_fib_chain_proof_succeeds() {
     client.verify_proof(&public_inputs, &proof_bytes);
 }
 
+#[test]
+fn verify_simple_circuit_zk_proof_succeeds() {
+    let vk_bytes_raw: &[u8] = include_bytes!("simple_circuit/target/zk/vk");
+    let proof_bin: &[u8] = include_bytes!("simple_circuit/target/zk/proof");
+    let pub_inputs_bin: &[u8] = include_bytes!("simple_circuit/target/zk/public_inputs");
+
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    assert_eq!(proof_bin.len(), ZK_PROOF_BYTES);
+
+    // Prepare inputs
+    let vk_bytes = Bytes::from_slice(&env, vk_bytes_raw);
+    let proof_bytes: Bytes = Bytes::from_slice(&env, proof_bin);
+    let public_inputs: Bytes = Bytes::from_slice(&env, pub_inputs_bin);
+
+    let client = register_client(&env, &vk_bytes);
+    client.verify_proof(&public_inputs, &proof_bytes);
+}
+
+#[test]
+fn verify_simple_circuit_poseidon2_proof_succeeds() {
+    let vk_bytes_raw: &[u8] = include_bytes!("simple_circuit/target/poseidon2/vk");
+    let proof_bin: &[u8] = include_bytes!("simple_circuit/target/poseidon2/proof");
+    let pub_inputs_bin: &[u8] = include_bytes!("simple_circuit/target/poseidon2/public_inputs");
+
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    assert_eq!(proof_bin.len(), PROOF_BYTES);
+
+    // Prepare inputs
+    let vk_bytes = Bytes::from_slice(&env, vk_bytes_raw);
+    let proof_bytes: Bytes = Bytes::from_slice(&env, proof_bin);
+    let public_inputs: Bytes = Bytes::from_slice(&env, pub_inputs_bin);
+
+    let client = register_client_with(&env, &vk_bytes, ultrahonk_contract::OracleHash::Poseidon2);
+    client.verify_proof(&public_inputs, &proof_bytes);
+}
+
+/// BN254 scalar field modulus r (big-endian).
+const R_BE: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xf0, 0x00, 0x00, 0x01,
+];
+
+/// Add r to a 32-byte big-endian word: same field element, different bytes.
+fn add_scalar_modulus(word: &mut [u8]) {
+    let mut carry = 0u16;
+    for i in (0..32).rev() {
+        let sum = word[i] as u16 + R_BE[i] as u16 + carry;
+        word[i] = sum as u8;
+        carry = sum >> 8;
+    }
+    assert_eq!(carry, 0, "word + r overflows 256 bits");
+}
+
+#[test]
+fn verify_rejects_non_canonical_public_input() {
+    let vk_bytes_raw: &[u8] = include_bytes!("simple_circuit/target/vk");
+    let proof_bin: &[u8] = include_bytes!("simple_circuit/target/proof");
+    let pub_inputs_bin: &[u8] = include_bytes!("simple_circuit/target/public_inputs");
+
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+
+    let mut pub_inputs = pub_inputs_bin.to_vec();
+    add_scalar_modulus(&mut pub_inputs[..32]);
+
+    let vk_bytes = Bytes::from_slice(&env, vk_bytes_raw);
+    let proof_bytes: Bytes = Bytes::from_slice(&env, proof_bin);
+    let public_inputs: Bytes = Bytes::from_slice(&env, &pub_inputs);
+
+    let client = register_client(&env, &vk_bytes);
+    assert_eq!(
+        client.try_verify_proof(&public_inputs, &proof_bytes),
+        Err(Ok(ultrahonk_contract::Error::NonCanonicalEncoding))
+    );
+}
+
+#[test]
+fn staged_verification_succeeds() {
+    let vk_bytes_raw: &[u8] = include_bytes!("simple_circuit/target/vk");
+    let proof_bin: &[u8] = include_bytes!("simple_circuit/target/proof");
+    let pub_inputs_bin: &[u8] = include_bytes!("simple_circuit/target/public_inputs");
+
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+
+    let vk_bytes = Bytes::from_slice(&env, vk_bytes_raw);
+    let proof_bytes: Bytes = Bytes::from_slice(&env, proof_bin);
+    let public_inputs: Bytes = Bytes::from_slice(&env, pub_inputs_bin);
+
+    let client = register_client(&env, &vk_bytes);
+    let hash = client.verify_stage_transcript(&public_inputs, &proof_bytes);
+    assert!(!client.is_verified(&hash));
+
+    // Stages run in order only
+    assert_eq!(
+        client.try_verify_stage_final(&public_inputs, &proof_bytes),
+        Err(Ok(ultrahonk_contract::Error::StageOutOfOrder))
+    );
+    client.verify_stage_sumcheck(&public_inputs, &proof_bytes);
+    client.verify_stage_final(&public_inputs, &proof_bytes);
+    assert!(client.is_verified(&hash));
+
+    // The stage state is gone once verified; resubmitting stage 1 only
+    // returns the hash
+    assert_eq!(
+        client.try_verify_stage_final(&public_inputs, &proof_bytes),
+        Err(Ok(ultrahonk_contract::Error::StageOutOfOrder))
+    );
+    assert_eq!(
+        client.verify_stage_transcript(&public_inputs, &proof_bytes),
+        hash
+    );
+    assert_eq!(
+        client.try_verify_stage_sumcheck(&public_inputs, &proof_bytes),
+        Err(Ok(ultrahonk_contract::Error::StageOutOfOrder))
+    );
+
+    // The verified flag outlives the staging TTL
+    env.ledger().with_mut(|l| l.sequence_number += 20_000);
+    assert!(client.is_verified(&hash));
+}
+
+#[test]
+fn staged_verification_is_bound_to_the_proof() {
+    let vk_bytes_raw: &[u8] = include_bytes!("simple_circuit/target/vk");
+    let proof_bin: &[u8] = include_bytes!("simple_circuit/target/proof");
+    let pub_inputs_bin: &[u8] = include_bytes!("simple_circuit/target/public_inputs");
+
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+
+    let mut other_inputs = pub_inputs_bin.to_vec();
+    other_inputs[31] ^= 1;
+
+    let vk_bytes = Bytes::from_slice(&env, vk_bytes_raw);
+    let proof_bytes: Bytes = Bytes::from_slice(&env, proof_bin);
+    let public_inputs: Bytes = Bytes::from_slice(&env, pub_inputs_bin);
+    let other_inputs: Bytes = Bytes::from_slice(&env, &other_inputs);
+
+    let client = register_client(&env, &vk_bytes);
+    client.verify_stage_transcript(&public_inputs, &proof_bytes);
+    assert_eq!(
+        client.try_verify_stage_sumcheck(&other_inputs, &proof_bytes),
+        Err(Ok(ultrahonk_contract::Error::StageOutOfOrder))
+    );
+}
+
+#[test]
+fn proof_hash_separates_proof_from_public_inputs() {
+    let vk_bytes_raw: &[u8] = include_bytes!("simple_circuit/target/vk");
+    let proof_bin: &[u8] = include_bytes!("simple_circuit/target/proof");
+    let pub_inputs_bin: &[u8] = include_bytes!("simple_circuit/target/public_inputs");
+
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+
+    let vk_bytes = Bytes::from_slice(&env, vk_bytes_raw);
+    let proof_bytes: Bytes = Bytes::from_slice(&env, proof_bin);
+    let public_inputs: Bytes = Bytes::from_slice(&env, pub_inputs_bin);
+
+    let client = register_client(&env, &vk_bytes);
+    let hash = client.proof_hash(&public_inputs, &proof_bytes);
+    assert_eq!(
+        client.verify_stage_transcript(&public_inputs, &proof_bytes),
+        hash
+    );
+
+    // The last proof word moved into the public inputs: same concatenation,
+    // another statement
+    let split = proof_bin.len() - 32;
+    let mut moved = proof_bin[split..].to_vec();
+    moved.extend_from_slice(pub_inputs_bin);
+    let other = client.proof_hash(
+        &Bytes::from_slice(&env, &moved),
+        &Bytes::from_slice(&env, &proof_bin[..split]),
+    );
+    assert_ne!(other, hash);
+}
+
+#[test]
+fn verify_proofs_batch_succeeds() {
+    let vk_bytes_raw: &[u8] = include_bytes!("simple_circuit/target/vk");
+    let proof_bin: &[u8] = include_bytes!("simple_circuit/target/proof");
+    let pub_inputs_bin: &[u8] = include_bytes!("simple_circuit/target/public_inputs");
+
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+
+    let vk_bytes = Bytes::from_slice(&env, vk_bytes_raw);
+    let proof_bytes: Bytes = Bytes::from_slice(&env, proof_bin);
+    let public_inputs: Bytes = Bytes::from_slice(&env, pub_inputs_bin);
+
+    let client = register_client(&env, &vk_bytes);
+    let entry = (public_inputs, proof_bytes);
+    client.verify_proofs(&vec![&env, entry.clone(), entry.clone()]);
+    assert_eq!(
+        client.try_verify_proofs(&vec![&env]),
+        Err(Ok(ultrahonk_contract::Error::VerificationFailed))
+    );
+}
+
+#[test]
+fn verify_compact_proof_succeeds() {
+    let vk_bytes_raw: &[u8] = include_bytes!("simple_circuit/target/vk");
+    let proof_bin: &[u8] = include_bytes!("simple_circuit/target/proof");
+    let pub_inputs_bin: &[u8] = include_bytes!("simple_circuit/target/public_inputs");
+
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+
+    let vk_bytes = Bytes::from_slice(&env, vk_bytes_raw);
+    let log_n = load_vk_from_bytes(&vk_bytes).unwrap().log_circuit_size as usize;
+    let compact = compact_proof(&proof_bin.to_vec(), log_n).unwrap();
+    assert!(compact.len() < PROOF_BYTES);
+    let proof_bytes: Bytes = Bytes::from_slice(&env, &compact);
+    let public_inputs: Bytes = Bytes::from_slice(&env, pub_inputs_bin);
+
+    let client = register_client(&env, &vk_bytes);
+    client.verify_compact_proof(&public_inputs, &proof_bytes);
+    assert_eq!(
+        client.try_verify_compact_proof(&public_inputs, &Bytes::from_slice(&env, proof_bin)),
+        Err(Ok(ultrahonk_contract::Error::ProofParseError))
+    );
+}
+
 #[test]
 fn print_budget_for_deploy_and_verify() {
     let vk_bytes_raw: &[u8] = include_bytes!("simple_circuit/target/vk");
@@ -78,3 +319,76 @@ fn print_budget_for_deploy_and_verify() {
     println!("=== verify_proof budget usage ===");
     env.cost_estimate().budget().print();
 }
+
+#[test]
+fn sumcheck_stage_cost_below_runtime_parsed_baseline() {
+    // Sum-check is where the relation and barycentric constants are used;
+    // measuring the stage alone keeps its wasm cost apart from the host
+    // curve operations of Shplemini. The baseline parses those constants
+    // from hex at run time; see ci.yml.
+    let vk_bytes_raw: &[u8] = include_bytes!("simple_circuit/target/vk");
+    let proof_bin: &[u8] = include_bytes!("simple_circuit/target/proof");
+    let pub_inputs_bin: &[u8] = include_bytes!("simple_circuit/target/public_inputs");
+
+    let mut cpu = Vec::new();
+    for (name, wasm) in [
+        ("runtime-parsed", CONTRACT_WASM_SUMCHECK_BASELINE),
+        ("compile-time", CONTRACT_WASM),
+    ] {
+        let env = Env::default();
+        env.cost_estimate().budget().reset_unlimited();
+        let vk_bytes = Bytes::from_slice(&env, vk_bytes_raw);
+        let proof_bytes: Bytes = Bytes::from_slice(&env, proof_bin);
+        let public_inputs: Bytes = Bytes::from_slice(&env, pub_inputs_bin);
+        let contract_id = env.register(wasm, (vk_bytes, ultrahonk_contract::OracleHash::Keccak));
+        let client = ultrahonk_contract::Client::new(&env, &contract_id);
+        client.verify_stage_transcript(&public_inputs, &proof_bytes);
+
+        env.cost_estimate().budget().reset_unlimited();
+        client.verify_stage_sumcheck(&public_inputs, &proof_bytes);
+        let budget = env.cost_estimate().budget();
+        let (c, m) = (budget.cpu_instruction_cost(), budget.memory_bytes_cost());
+        println!("=== verify_stage_sumcheck, {name} constants: cpu {c}, mem {m} ===");
+        cpu.push(c);
+    }
+    println!(
+        "=== sum-check cpu delta: -{} ===",
+        cpu[0].saturating_sub(cpu[1])
+    );
+    assert!(
+        cpu[1] < cpu[0],
+        "compile-time {} >= runtime-parsed {}",
+        cpu[1],
+        cpu[0]
+    );
+}
+
+#[test]
+fn msm_backend_cost_comparison() {
+    // The same contract built with `--features msm-per-term`; see ci.yml.
+    let vk_bytes_raw: &[u8] = include_bytes!("simple_circuit/target/vk");
+    let proof_bin: &[u8] = include_bytes!("simple_circuit/target/proof");
+    let pub_inputs_bin: &[u8] = include_bytes!("simple_circuit/target/public_inputs");
+
+    let mut cpu = Vec::new();
+    for (name, wasm) in [
+        ("PerTerm", CONTRACT_WASM_MSM_PER_TERM),
+        ("Windowed", CONTRACT_WASM),
+    ] {
+        let env = Env::default();
+        env.cost_estimate().budget().reset_unlimited();
+        let vk_bytes = Bytes::from_slice(&env, vk_bytes_raw);
+        let proof_bytes: Bytes = Bytes::from_slice(&env, proof_bin);
+        let public_inputs: Bytes = Bytes::from_slice(&env, pub_inputs_bin);
+        let contract_id = env.register(wasm, (vk_bytes, ultrahonk_contract::OracleHash::Keccak));
+        let client = ultrahonk_contract::Client::new(&env, &contract_id);
+
+        env.cost_estimate().budget().reset_unlimited();
+        client.verify_proof(&public_inputs, &proof_bytes);
+        let budget = env.cost_estimate().budget();
+        let (c, m) = (budget.cpu_instruction_cost(), budget.memory_bytes_cost());
+        println!("=== verify_proof with {name} MSM: cpu {c}, mem {m} ===");
+        cpu.push(c);
+    }
+    assert!(cpu[1] < cpu[0], "Windowed {} >= PerTerm {}", cpu[1], cpu[0]);
+}
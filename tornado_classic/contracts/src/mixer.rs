@@ -6,7 +6,7 @@ use soroban_sdk::{
     contract, contracterror, contractevent, contractimpl, crypto::BnScalar, symbol_short, Address,
     Bytes, BytesN, Env, InvokeError, IntoVal, Symbol, U256, Vec as SorobanVec, Val,
 };
-use ultrahonk_soroban_verifier::PROOF_BYTES;
+use ultrahonk_soroban_verifier::{format::ProofFormat, public_inputs::PublicInputs};
 
 #[contract]
 pub struct MixerContract;
@@ -72,16 +72,22 @@ fn zeroes_for_tree(env: &Env) -> Vec<BytesN<32>> {
     zeroes
 }
 
+// Public input indices of the circuit's `main(root: pub Field, nullifier_hash: pub Field, ...)`.
+const PI_ROOT: usize = 0;
+const PI_NULLIFIER_HASH: usize = 1;
+const PI_COUNT: usize = 2;
+
 fn parse_public_inputs(bytes: &Bytes) -> Result<([u8; 32], [u8; 32]), MixerError> {
-    if bytes.len() != 64 {
+    let inputs = PublicInputs::new(bytes).map_err(|_| MixerError::VerificationFailed)?;
+    if inputs.len() != PI_COUNT {
         return Err(MixerError::VerificationFailed);
     }
-    let mut buf = [0u8; 64];
-    bytes.copy_into_slice(&mut buf);
-    let mut root = [0u8; 32];
-    root.copy_from_slice(&buf[..32]);
-    let mut nullifier_hash = [0u8; 32];
-    nullifier_hash.copy_from_slice(&buf[32..]);
+    let root = inputs
+        .field(PI_ROOT)
+        .map_err(|_| MixerError::VerificationFailed)?;
+    let nullifier_hash = inputs
+        .field(PI_NULLIFIER_HASH)
+        .map_err(|_| MixerError::VerificationFailed)?;
     Ok((root, nullifier_hash))
 }
 
@@ -169,7 +175,7 @@ impl MixerContract {
         public_inputs: Bytes,
         proof_bytes: Bytes,
     ) -> Result<(), MixerError> {
-        if proof_bytes.len() as usize != PROOF_BYTES {
+        if !ProofFormat::is_known_len(proof_bytes.len() as usize) {
             return Err(MixerError::VerificationFailed);
         }
         // Interpret public inputs as `[root, nullifier_hash]`.
@@ -8,7 +8,7 @@ use soroban_sdk::{
 use std::sync::{Mutex, OnceLock};
 
 use tornado_classic_contracts::mixer::{MixerContract, MixerError};
-use rs_soroban_ultrahonk::UltraHonkVerifierContract;
+use rs_soroban_ultrahonk::{OracleHash, UltraHonkVerifierContract};
 use ultrahonk_soroban_verifier::PROOF_BYTES;
 
 const TREE_DEPTH_TEST: u32 = 20;
@@ -96,7 +96,10 @@ fn frontier_root_from_leaves(env: &Env, leaves: &[[u8; 32]], depth: u32) -> [u8;
 }
 
 fn register_verifier(env: &Env, vk_bytes: &Bytes) -> Address {
-    env.register(UltraHonkVerifierContract, (vk_bytes.clone(),))
+    env.register(
+        UltraHonkVerifierContract,
+        (vk_bytes.clone(), OracleHash::Keccak),
+    )
 }
 fn register_mixer(env: &Env, verifier: Address) -> Address {
     env.register(MixerContract, (verifier,))
@@ -107,7 +110,13 @@ fn register_wasm_verifier<'a>(
     env: &'a Env,
     vk_bytes: &Bytes,
 ) -> (wasm_artifacts::ultrahonk_contract::Client<'a>, Address) {
-    let contract_id = env.register(wasm_artifacts::VERIFIER_WASM, (vk_bytes.clone(),));
+    let contract_id = env.register(
+        wasm_artifacts::VERIFIER_WASM,
+        (
+            vk_bytes.clone(),
+            wasm_artifacts::ultrahonk_contract::OracleHash::Keccak,
+        ),
+    );
     (wasm_artifacts::ultrahonk_contract::Client::new(env, &contract_id), contract_id)
 }
 
@@ -3,7 +3,7 @@ use soroban_sdk::{Address, Bytes, Env};
 
 use std::sync::{Mutex, OnceLock};
 
-use rs_soroban_ultrahonk::UltraHonkVerifierContract;
+use rs_soroban_ultrahonk::{OracleHash, UltraHonkVerifierContract};
 use ultrahonk_soroban_verifier::PROOF_BYTES;
 
 fn verify_lock() -> &'static Mutex<()> {
@@ -25,7 +25,10 @@ fn verify_proof_with_constructor_vk() {
     assert_eq!(proof_bin.len(), PROOF_BYTES);
 
     let vk_bytes: Bytes = Bytes::from_slice(&env, vk_bin);
-    let verifier_id: Address = env.register(UltraHonkVerifierContract, (vk_bytes.clone(),));
+    let verifier_id: Address = env.register(
+        UltraHonkVerifierContract,
+        (vk_bytes.clone(), OracleHash::Keccak),
+    );
     let proof_bytes: Bytes = Bytes::from_slice(&env, proof_bin);
     let public_inputs: Bytes = Bytes::from_slice(&env, pub_inputs_bin);
 
@@ -0,0 +1,374 @@
+//! Noir ABI codec for public inputs: reads the ABI of a nargo artifact
+//! (`target/<circuit>.json`) and converts typed values to and from the
+//! `public_inputs` bytes bb writes.
+//!
+//! bb lays the public parameters out in declaration order, then the return
+//! value, each flattened to 32-byte big-endian words: arrays and tuples
+//! element by element, structs field by field, strings one word per byte.
+//! Booleans are 0 / 1 and signed integers are two's complement in their
+//! bit width.
+
+use crate::field::Fr;
+use serde_json::Value;
+
+/// Why an ABI could not be read or a value not encoded / decoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AbiError {
+    /// The JSON is not a nargo ABI, or uses a type this codec lacks.
+    BadAbi,
+    /// A public parameter or struct field has no value.
+    MissingValue,
+    /// A value (or decoded word) does not fit its ABI type.
+    TypeMismatch,
+    /// The bytes are not `PublicAbi::num_fields` words.
+    WrongLength,
+}
+
+/// A Noir ABI type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AbiType {
+    Field,
+    Integer {
+        signed: bool,
+        width: u32,
+    },
+    Boolean,
+    String {
+        length: usize,
+    },
+    Array {
+        length: usize,
+        typ: Box<AbiType>,
+    },
+    Tuple(Vec<AbiType>),
+    Struct {
+        path: String,
+        fields: Vec<(String, AbiType)>,
+    },
+}
+
+/// A value of an [`AbiType`]. Tuples are `Array`s of their fields.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AbiValue {
+    Field(Fr),
+    Unsigned(u128),
+    Signed(i64),
+    Boolean(bool),
+    String(String),
+    Array(Vec<AbiValue>),
+    Struct(Vec<(String, AbiValue)>),
+}
+
+/// The public part of a circuit's ABI.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PublicAbi {
+    /// Public parameters of `main`, in declaration order.
+    pub parameters: Vec<(String, AbiType)>,
+    pub return_type: Option<AbiType>,
+}
+
+impl AbiType {
+    /// Public input words the type flattens to.
+    pub fn num_fields(&self) -> usize {
+        match self {
+            AbiType::Field | AbiType::Integer { .. } | AbiType::Boolean => 1,
+            AbiType::String { length } => *length,
+            AbiType::Array { length, typ } => length * typ.num_fields(),
+            AbiType::Tuple(fields) => fields.iter().map(AbiType::num_fields).sum(),
+            AbiType::Struct { fields, .. } => fields.iter().map(|(_, t)| t.num_fields()).sum(),
+        }
+    }
+
+    fn from_json(v: &Value) -> Result<Self, AbiError> {
+        let length = || {
+            v["length"]
+                .as_u64()
+                .map(|n| n as usize)
+                .ok_or(AbiError::BadAbi)
+        };
+        Ok(match v["kind"].as_str().ok_or(AbiError::BadAbi)? {
+            "field" => AbiType::Field,
+            "boolean" => AbiType::Boolean,
+            "integer" => {
+                let signed = match v["sign"].as_str() {
+                    Some("unsigned") => false,
+                    Some("signed") => true,
+                    _ => return Err(AbiError::BadAbi),
+                };
+                let width = v["width"].as_u64().ok_or(AbiError::BadAbi)? as u32;
+                if width == 0 || width > if signed { 64 } else { 128 } {
+                    return Err(AbiError::BadAbi);
+                }
+                AbiType::Integer { signed, width }
+            }
+            "string" => AbiType::String { length: length()? },
+            "array" => AbiType::Array {
+                length: length()?,
+                typ: Box::new(AbiType::from_json(&v["type"])?),
+            },
+            "tuple" => AbiType::Tuple(
+                json_array(&v["fields"])?
+                    .iter()
+                    .map(AbiType::from_json)
+                    .collect::<Result<_, _>>()?,
+            ),
+            "struct" => AbiType::Struct {
+                path: v["path"].as_str().ok_or(AbiError::BadAbi)?.to_string(),
+                fields: json_array(&v["fields"])?
+                    .iter()
+                    .map(named_type)
+                    .collect::<Result<_, _>>()?,
+            },
+            _ => return Err(AbiError::BadAbi),
+        })
+    }
+
+    fn encode(&self, value: &AbiValue, out: &mut Vec<u8>) -> Result<(), AbiError> {
+        match (self, value) {
+            (AbiType::Field, AbiValue::Field(f)) => out.extend_from_slice(&f.to_bytes()),
+            (
+                AbiType::Integer {
+                    signed: false,
+                    width,
+                },
+                AbiValue::Unsigned(v),
+            ) => {
+                if *width < 128 && *v >> width != 0 {
+                    return Err(AbiError::TypeMismatch);
+                }
+                push_u128(out, *v);
+            }
+            (
+                AbiType::Integer {
+                    signed: true,
+                    width,
+                },
+                AbiValue::Signed(v),
+            ) => {
+                let half = 1i128 << (width - 1);
+                let v = *v as i128;
+                if v < -half || v >= half {
+                    return Err(AbiError::TypeMismatch);
+                }
+                push_u128(out, v.rem_euclid(2 * half) as u128);
+            }
+            (AbiType::Boolean, AbiValue::Boolean(b)) => push_u128(out, *b as u128),
+            (AbiType::String { length }, AbiValue::String(s)) => {
+                if s.len() != *length {
+                    return Err(AbiError::TypeMismatch);
+                }
+                for &b in s.as_bytes() {
+                    push_u128(out, b as u128);
+                }
+            }
+            (AbiType::Array { length, typ }, AbiValue::Array(items)) => {
+                if items.len() != *length {
+                    return Err(AbiError::TypeMismatch);
+                }
+                for item in items {
+                    typ.encode(item, out)?;
+                }
+            }
+            (AbiType::Tuple(types), AbiValue::Array(items)) => {
+                if items.len() != types.len() {
+                    return Err(AbiError::TypeMismatch);
+                }
+                for (typ, item) in types.iter().zip(items) {
+                    typ.encode(item, out)?;
+                }
+            }
+            (AbiType::Struct { fields, .. }, AbiValue::Struct(values)) => {
+                for (name, typ) in fields {
+                    typ.encode(lookup(values, name)?, out)?;
+                }
+            }
+            _ => return Err(AbiError::TypeMismatch),
+        }
+        Ok(())
+    }
+
+    /// Decode from the front of `words`, advancing past what was read.
+    fn decode(&self, words: &mut core::slice::ChunksExact<'_, u8>) -> Result<AbiValue, AbiError> {
+        let mut next = || {
+            let mut word = [0u8; 32];
+            word.copy_from_slice(words.next().ok_or(AbiError::WrongLength)?);
+            Ok::<_, AbiError>(word)
+        };
+        Ok(match self {
+            AbiType::Field => {
+                AbiValue::Field(Fr::from_bytes_canonical(&next()?).ok_or(AbiError::TypeMismatch)?)
+            }
+            AbiType::Integer { signed, width } => {
+                let v = word_to_u128(&next()?)?;
+                if *width < 128 && v >> width != 0 {
+                    return Err(AbiError::TypeMismatch);
+                }
+                if *signed {
+                    let modulus = 1i128 << width;
+                    let v = v as i128;
+                    AbiValue::Signed(if v >= modulus / 2 { v - modulus } else { v } as i64)
+                } else {
+                    AbiValue::Unsigned(v)
+                }
+            }
+            AbiType::Boolean => match word_to_u128(&next()?)? {
+                0 => AbiValue::Boolean(false),
+                1 => AbiValue::Boolean(true),
+                _ => return Err(AbiError::TypeMismatch),
+            },
+            AbiType::String { length } => {
+                let mut bytes = Vec::with_capacity(*length);
+                for _ in 0..*length {
+                    let b = word_to_u128(&next()?)?;
+                    bytes.push(u8::try_from(b).map_err(|_| AbiError::TypeMismatch)?);
+                }
+                AbiValue::String(String::from_utf8(bytes).map_err(|_| AbiError::TypeMismatch)?)
+            }
+            AbiType::Array { length, typ } => AbiValue::Array(
+                (0..*length)
+                    .map(|_| typ.decode(words))
+                    .collect::<Result<_, _>>()?,
+            ),
+            AbiType::Tuple(types) => AbiValue::Array(
+                types
+                    .iter()
+                    .map(|t| t.decode(words))
+                    .collect::<Result<_, _>>()?,
+            ),
+            AbiType::Struct { fields, .. } => AbiValue::Struct(
+                fields
+                    .iter()
+                    .map(|(name, t)| Ok((name.clone(), t.decode(words)?)))
+                    .collect::<Result<_, AbiError>>()?,
+            ),
+        })
+    }
+}
+
+impl PublicAbi {
+    /// Read the public parameters and return type from a nargo artifact, or
+    /// from its bare `abi` object.
+    pub fn from_json(json: &str) -> Result<Self, AbiError> {
+        let root: Value = serde_json::from_str(json).map_err(|_| AbiError::BadAbi)?;
+        let abi = root.get("abi").unwrap_or(&root);
+
+        let mut parameters = Vec::new();
+        for p in json_array(&abi["parameters"])? {
+            if p["visibility"].as_str() == Some("public") {
+                parameters.push(named_type(p)?);
+            }
+        }
+        let return_type = match &abi["return_type"] {
+            Value::Null => None,
+            r => Some(AbiType::from_json(&r["abi_type"])?),
+        };
+        Ok(Self {
+            parameters,
+            return_type,
+        })
+    }
+
+    /// Public input words in total.
+    pub fn num_fields(&self) -> usize {
+        self.parameters
+            .iter()
+            .map(|(_, t)| t.num_fields())
+            .chain(self.return_type.iter().map(AbiType::num_fields))
+            .sum()
+    }
+
+    /// Index of the first word of public parameter `name`, for
+    /// [`PublicInputs`](crate::public_inputs::PublicInputs) on-chain.
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        let mut index = 0;
+        for (n, t) in &self.parameters {
+            if n == name {
+                return Some(index);
+            }
+            index += t.num_fields();
+        }
+        None
+    }
+
+    /// Index of the first word of the return value.
+    pub fn return_index(&self) -> Option<usize> {
+        self.return_type
+            .as_ref()
+            .map(|_| self.parameters.iter().map(|(_, t)| t.num_fields()).sum())
+    }
+
+    /// `public_inputs` bytes for `inputs` (by parameter name, any order)
+    /// and the return value, if the circuit has one.
+    pub fn encode(
+        &self,
+        inputs: &[(String, AbiValue)],
+        return_value: Option<&AbiValue>,
+    ) -> Result<Vec<u8>, AbiError> {
+        let mut out = Vec::with_capacity(self.num_fields() * 32);
+        for (name, typ) in &self.parameters {
+            typ.encode(lookup(inputs, name)?, &mut out)?;
+        }
+        match (&self.return_type, return_value) {
+            (Some(typ), Some(value)) => typ.encode(value, &mut out)?,
+            (None, None) => {}
+            (Some(_), None) => return Err(AbiError::MissingValue),
+            (None, Some(_)) => return Err(AbiError::TypeMismatch),
+        }
+        Ok(out)
+    }
+
+    /// Inverse of [`PublicAbi::encode`]: the public parameters in
+    /// declaration order, then the return value.
+    #[allow(clippy::type_complexity)]
+    pub fn decode(
+        &self,
+        bytes: &[u8],
+    ) -> Result<(Vec<(String, AbiValue)>, Option<AbiValue>), AbiError> {
+        if bytes.len() != self.num_fields() * 32 {
+            return Err(AbiError::WrongLength);
+        }
+        let mut words = bytes.chunks_exact(32);
+        let inputs = self
+            .parameters
+            .iter()
+            .map(|(name, t)| Ok((name.clone(), t.decode(&mut words)?)))
+            .collect::<Result<_, AbiError>>()?;
+        let return_value = match &self.return_type {
+            Some(t) => Some(t.decode(&mut words)?),
+            None => None,
+        };
+        Ok((inputs, return_value))
+    }
+}
+
+fn json_array(v: &Value) -> Result<&Vec<Value>, AbiError> {
+    v.as_array().ok_or(AbiError::BadAbi)
+}
+
+/// A `{ "name": ..., "type": ... }` entry.
+fn named_type(v: &Value) -> Result<(String, AbiType), AbiError> {
+    let name = v["name"].as_str().ok_or(AbiError::BadAbi)?;
+    Ok((name.to_string(), AbiType::from_json(&v["type"])?))
+}
+
+fn lookup<'a>(values: &'a [(String, AbiValue)], name: &str) -> Result<&'a AbiValue, AbiError> {
+    values
+        .iter()
+        .find(|(n, _)| n == name)
+        .map(|(_, v)| v)
+        .ok_or(AbiError::MissingValue)
+}
+
+fn push_u128(out: &mut Vec<u8>, v: u128) {
+    out.extend_from_slice(&[0u8; 16]);
+    out.extend_from_slice(&v.to_be_bytes());
+}
+
+fn word_to_u128(word: &[u8; 32]) -> Result<u128, AbiError> {
+    if word[..16].iter().any(|&b| b != 0) {
+        return Err(AbiError::TypeMismatch);
+    }
+    let mut tail = [0u8; 16];
+    tail.copy_from_slice(&word[16..]);
+    Ok(u128::from_be_bytes(tail))
+}
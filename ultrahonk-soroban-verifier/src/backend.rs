@@ -0,0 +1,283 @@
+//! Crypto backends the verifier runs on: the Soroban host (`Env`) for
+//! contracts, or pure `ark-bn254` (`native` feature) for off-chain services
+//! that want to check a proof without a simulated host.
+
+use crate::{
+    field::Fr,
+    hash::{hash32, poseidon2_hash32},
+    types::{G1Point, ParseError},
+};
+use soroban_sdk::{
+    crypto::bn254::{Bn254G1Affine, Bn254G2Affine, Fr as Bn254Fr},
+    Bytes, BytesN, Env, Vec as HostVec,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Byte buffer holding verifier inputs and hash preimages.
+pub trait ByteBuf: Clone {
+    fn len(&self) -> u32;
+    /// Copy `out.len()` bytes starting at `offset` into `out`.
+    fn read_into(&self, offset: u32, out: &mut [u8]);
+    /// The bytes in `start..end`.
+    fn range(&self, start: u32, end: u32) -> Self;
+    fn extend_from_slice(&mut self, data: &[u8]);
+    fn append(&mut self, other: &Self);
+    /// Replace the contents with `prefix`.
+    fn reset(&mut self, prefix: &[u8]);
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl ByteBuf for Bytes {
+    fn len(&self) -> u32 {
+        Bytes::len(self)
+    }
+
+    fn read_into(&self, offset: u32, out: &mut [u8]) {
+        self.slice(offset..offset + out.len() as u32)
+            .copy_into_slice(out);
+    }
+
+    fn range(&self, start: u32, end: u32) -> Self {
+        self.slice(start..end)
+    }
+
+    fn extend_from_slice(&mut self, data: &[u8]) {
+        Bytes::extend_from_slice(self, data)
+    }
+
+    fn append(&mut self, other: &Self) {
+        Bytes::append(self, other)
+    }
+
+    fn reset(&mut self, prefix: &[u8]) {
+        // Host bytes are immutable: build the prefix as one new object
+        // rather than an empty one plus an extension.
+        *self = Bytes::from_slice(self.env(), prefix);
+    }
+}
+
+impl ByteBuf for Vec<u8> {
+    fn len(&self) -> u32 {
+        <[u8]>::len(self) as u32
+    }
+
+    fn read_into(&self, offset: u32, out: &mut [u8]) {
+        let start = offset as usize;
+        out.copy_from_slice(&self[start..start + out.len()]);
+    }
+
+    fn range(&self, start: u32, end: u32) -> Self {
+        self[start as usize..end as usize].to_vec()
+    }
+
+    fn extend_from_slice(&mut self, data: &[u8]) {
+        Vec::extend_from_slice(self, data)
+    }
+
+    fn append(&mut self, other: &Self) {
+        Vec::extend_from_slice(self, other)
+    }
+
+    fn reset(&mut self, prefix: &[u8]) {
+        Vec::clear(self);
+        Vec::extend_from_slice(self, prefix)
+    }
+}
+
+/// Hashing, BN254 G1 arithmetic and pairing used by the verifier.
+pub trait Backend: Clone {
+    type Bytes: ByteBuf;
+    /// Affine G1 point in the backend's representation.
+    type G1: Clone + core::fmt::Debug;
+    type G2: Clone + core::fmt::Debug;
+
+    /// An empty byte buffer.
+    fn bytes(&self) -> Self::Bytes;
+    fn keccak256(&self, data: &Self::Bytes) -> [u8; 32];
+    /// Whether [`Backend::poseidon2`] is available.
+    const HAS_POSEIDON2: bool = true;
+    /// bb's Poseidon2 sponge over 32-byte words, `None` if unsupported.
+    fn poseidon2(&self, data: &Self::Bytes) -> Option<[u8; 32]>;
+
+    fn g1(&self, pt: &G1Point) -> Result<Self::G1, ParseError>;
+    /// Uncompressed `x ‖ y`, 64 zero bytes for infinity.
+    fn g1_to_bytes(&self, pt: &Self::G1) -> [u8; 64];
+    fn g1_add(&self, a: &Self::G1, b: &Self::G1) -> Self::G1;
+    fn g1_mul(&self, pt: &Self::G1, scalar: &Fr) -> Self::G1;
+    fn g1_neg(&self, pt: &Self::G1) -> Self::G1;
+
+    /// G2 point from its 128-byte `x ‖ y` encoding, `c1 ‖ c0` per coordinate.
+    fn g2(&self, bytes: &[u8; 128]) -> Self::G2;
+    /// ∏ e(g1ᵢ, g2ᵢ) == 1
+    fn pairing_check(&self, g1: &[Self::G1], g2: &[Self::G2]) -> bool;
+
+    fn bytes_from_slice(&self, data: &[u8]) -> Self::Bytes {
+        let mut out = self.bytes();
+        out.extend_from_slice(data);
+        out
+    }
+}
+
+#[inline(always)]
+fn fr_to_bn254(env: &Env, fr: &Fr) -> Bn254Fr {
+    Bn254Fr::from_bytes(BytesN::from_array(env, &fr.to_bytes()))
+}
+
+/// The Soroban host functions.
+impl Backend for Env {
+    type Bytes = Bytes;
+    type G1 = Bn254G1Affine;
+    type G2 = Bn254G2Affine;
+
+    fn bytes(&self) -> Bytes {
+        Bytes::new(self)
+    }
+
+    fn keccak256(&self, data: &Bytes) -> [u8; 32] {
+        hash32(data)
+    }
+
+    fn poseidon2(&self, data: &Bytes) -> Option<[u8; 32]> {
+        Some(poseidon2_hash32(data))
+    }
+
+    // The host checks the point when it is first used
+    #[inline(always)]
+    fn g1(&self, pt: &G1Point) -> Result<Bn254G1Affine, ParseError> {
+        Ok(Bn254G1Affine::from_array(self, &pt.to_bytes()))
+    }
+
+    fn g1_to_bytes(&self, pt: &Bn254G1Affine) -> [u8; 64] {
+        pt.to_array()
+    }
+
+    fn g1_add(&self, a: &Bn254G1Affine, b: &Bn254G1Affine) -> Bn254G1Affine {
+        self.crypto().bn254().g1_add(a, b)
+    }
+
+    fn g1_mul(&self, pt: &Bn254G1Affine, scalar: &Fr) -> Bn254G1Affine {
+        self.crypto().bn254().g1_mul(pt, &fr_to_bn254(self, scalar))
+    }
+
+    fn g1_neg(&self, pt: &Bn254G1Affine) -> Bn254G1Affine {
+        -pt
+    }
+
+    fn g2(&self, bytes: &[u8; 128]) -> Bn254G2Affine {
+        Bn254G2Affine::from_array(self, bytes)
+    }
+
+    fn pairing_check(&self, g1: &[Bn254G1Affine], g2: &[Bn254G2Affine]) -> bool {
+        let mut g1s: HostVec<Bn254G1Affine> = HostVec::new(self);
+        for p in g1 {
+            g1s.push_back(p.clone());
+        }
+        let mut g2s: HostVec<Bn254G2Affine> = HostVec::new(self);
+        for q in g2 {
+            g2s.push_back(q.clone());
+        }
+        self.crypto().bn254().pairing_check(g1s, g2s)
+    }
+}
+
+#[cfg(feature = "native")]
+pub use native::NativeBackend;
+
+#[cfg(feature = "native")]
+mod native {
+    use super::{Backend, G1Point, ParseError};
+    use crate::field::Fr;
+    use crate::utils::check_g1_point;
+    use ark_bn254::{Bn254, Fq, Fq2, G1Affine, G2Affine};
+    use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+    use ark_ff::{BigInteger, PrimeField, Zero};
+    use sha3::{Digest, Keccak256};
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
+
+    /// Pure `ark-bn254` backend. Poseidon2 is unsupported: the verifier
+    /// returns `VerifyError::InvalidInput` for a Poseidon2 oracle hash, so
+    /// only Keccak-transcript proofs verify on it.
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct NativeBackend;
+
+    fn fq(bytes: &[u8]) -> Fq {
+        Fq::from_be_bytes_mod_order(bytes)
+    }
+
+    fn fq_bytes(x: &Fq) -> [u8; 32] {
+        let mut out = [0u8; 32];
+        out.copy_from_slice(&x.into_bigint().to_bytes_be());
+        out
+    }
+
+    impl Backend for NativeBackend {
+        type Bytes = Vec<u8>;
+        type G1 = G1Affine;
+        type G2 = G2Affine;
+
+        fn bytes(&self) -> Vec<u8> {
+            Vec::new()
+        }
+
+        fn keccak256(&self, data: &Vec<u8>) -> [u8; 32] {
+            Keccak256::digest(data).into()
+        }
+
+        const HAS_POSEIDON2: bool = false;
+
+        fn poseidon2(&self, _data: &Vec<u8>) -> Option<[u8; 32]> {
+            None
+        }
+
+        fn g1(&self, pt: &G1Point) -> Result<G1Affine, ParseError> {
+            check_g1_point(pt.x, pt.y)?;
+            if *pt == G1Point::infinity() {
+                return Ok(G1Affine::identity());
+            }
+            // On the curve, and G1 has cofactor 1
+            Ok(G1Affine::new_unchecked(fq(&pt.x), fq(&pt.y)))
+        }
+
+        fn g1_to_bytes(&self, pt: &G1Affine) -> [u8; 64] {
+            let mut out = [0u8; 64];
+            if let Some((x, y)) = pt.xy() {
+                out[..32].copy_from_slice(&fq_bytes(&x));
+                out[32..].copy_from_slice(&fq_bytes(&y));
+            }
+            out
+        }
+
+        fn g1_add(&self, a: &G1Affine, b: &G1Affine) -> G1Affine {
+            (*a + *b).into_affine()
+        }
+
+        fn g1_mul(&self, pt: &G1Affine, scalar: &Fr) -> G1Affine {
+            (*pt * scalar.0).into_affine()
+        }
+
+        fn g1_neg(&self, pt: &G1Affine) -> G1Affine {
+            -*pt
+        }
+
+        // Only fed the fixed verifier SRS points, so no subgroup check
+        fn g2(&self, bytes: &[u8; 128]) -> G2Affine {
+            if bytes.iter().all(|&b| b == 0) {
+                return G2Affine::identity();
+            }
+            let x = Fq2::new(fq(&bytes[32..64]), fq(&bytes[..32]));
+            let y = Fq2::new(fq(&bytes[96..]), fq(&bytes[64..96]));
+            G2Affine::new_unchecked(x, y)
+        }
+
+        fn pairing_check(&self, g1: &[G1Affine], g2: &[G2Affine]) -> bool {
+            Bn254::multi_pairing(g1.iter().copied(), g2.iter().copied()).is_zero()
+        }
+    }
+}
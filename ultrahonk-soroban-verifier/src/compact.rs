@@ -0,0 +1,167 @@
+//! Compact encoding of plain bb v0.87.0 proofs, for cheaper calldata.
+//!
+//! bb v0.87.0 splits every G1 coordinate into two 32-byte limbs and pads
+//! sum-check and Gemini to `CONST_PROOF_SIZE_LOG_N` rounds, so every proof
+//! is `PROOF_BYTES` long. The compact encoding keeps the sections in the
+//! same order but writes each G1 point as its (x, y) coordinates and only
+//! the first `log_n` rounds, followed by one padding round copied verbatim:
+//! the round univariate, the fold commitment as four limbs and the Gemini
+//! evaluation ([`PADDING_ROUND_WORDS`] words).
+//!
+//! The Keccak transcript hashes the proof as bb serialized it, so it cannot
+//! run over the compact bytes. [`expand_compact_proof`] rebuilds the
+//! original v0.87.0 bytes exactly: limb splitting is one-to-one on
+//! canonical coordinates, and every padding round equals the stored one
+//! ([`compact_proof`] refuses proofs where they differ).
+
+use crate::backend::ByteBuf;
+use crate::flavor::UltraFlavor;
+use crate::format::{ProofFormat, NUMBER_OF_WITNESS_COMMITMENTS};
+use crate::types::{
+    ParseError, BATCHED_RELATION_PARTIAL_LENGTH, CONST_PROOF_SIZE_LOG_N, NUMBER_OF_ENTITIES,
+    PAIRING_POINTS_SIZE,
+};
+use crate::utils::{coord_to_halves_be, join_limbs};
+use crate::view::ProofView;
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+/// Words of the trailing padding round: univariate, limbed fold commitment
+/// and Gemini evaluation.
+pub const PADDING_ROUND_WORDS: usize = BATCHED_RELATION_PARTIAL_LENGTH + 4 + 1;
+
+/// Words before the padding round: pairing points, witness, fold and
+/// Shplonk commitments at two words each, `log_n` rounds of univariates,
+/// the sumcheck evaluations and `log_n` Gemini evaluations.
+const fn body_words(log_n: usize) -> usize {
+    PAIRING_POINTS_SIZE
+        + (NUMBER_OF_WITNESS_COMMITMENTS + log_n + 1) * 2
+        + log_n * BATCHED_RELATION_PARTIAL_LENGTH
+        + NUMBER_OF_ENTITIES
+        + log_n
+}
+
+/// Compact proof length in bytes for a circuit of size 2^`log_n`.
+pub const fn compact_proof_bytes(log_n: usize) -> usize {
+    (body_words(log_n) + PADDING_ROUND_WORDS) * 32
+}
+
+/// Convert a plain bb v0.87.0 proof for a circuit of size 2^`log_n` to the
+/// compact encoding. Off-chain: the whole proof is checked first.
+pub fn compact_proof(proof: &impl ByteBuf, log_n: usize) -> Result<Vec<u8>, ParseError> {
+    let mut bytes = vec![0u8; proof.len() as usize];
+    proof.read_into(0, &mut bytes);
+    let view = ProofView::new::<UltraFlavor>(&bytes, ProofFormat::V0_87, log_n)?;
+
+    let mut out = Vec::with_capacity(compact_proof_bytes(log_n));
+    let words = |out: &mut Vec<u8>, start: usize, n: usize| {
+        out.extend_from_slice(&bytes[start * 32..(start + n) * 32]);
+    };
+    let word = |w: usize| {
+        let mut out = [0u8; 32];
+        out.copy_from_slice(&bytes[w * 32..(w + 1) * 32]);
+        out
+    };
+    let points = |out: &mut Vec<u8>, start: usize, n: usize| {
+        for w in (start..start + 4 * n).step_by(2) {
+            out.extend_from_slice(&join_limbs(&word(w), &word(w + 1)));
+        }
+    };
+
+    words(&mut out, 0, PAIRING_POINTS_SIZE);
+    points(&mut out, view.witness_at(0), NUMBER_OF_WITNESS_COMMITMENTS);
+    words(
+        &mut out,
+        view.univariates_at(),
+        log_n * BATCHED_RELATION_PARTIAL_LENGTH,
+    );
+    words(&mut out, view.evaluations_at(), NUMBER_OF_ENTITIES);
+    points(&mut out, view.fold_comms_at(), log_n - 1);
+    words(&mut out, view.a_evaluations_at(), log_n);
+    points(&mut out, view.shplonk_q_at(), 2);
+
+    // One padding round, all of which must match it.
+    let sections = [
+        (
+            view.univariates_at(),
+            BATCHED_RELATION_PARTIAL_LENGTH,
+            log_n,
+            CONST_PROOF_SIZE_LOG_N,
+        ),
+        (
+            view.fold_comms_at(),
+            4,
+            log_n - 1,
+            CONST_PROOF_SIZE_LOG_N - 1,
+        ),
+        (view.a_evaluations_at(), 1, log_n, CONST_PROOF_SIZE_LOG_N),
+    ];
+    for (start, words, used, rounds) in sections {
+        let word_range = |r: usize| (start + r * words) * 32..(start + (r + 1) * words) * 32;
+        if used == rounds {
+            // log_n = CONST_PROOF_SIZE_LOG_N: nothing to pad
+            out.resize(out.len() + words * 32, 0);
+            continue;
+        }
+        let first = &bytes[word_range(used)];
+        if (used + 1..rounds).any(|r| bytes[word_range(r)] != *first) {
+            return Err(ParseError::IrregularPadding);
+        }
+        out.extend_from_slice(first);
+    }
+    Ok(out)
+}
+
+/// Rebuild the bb v0.87.0 bytes of a compact proof for a circuit of size
+/// 2^`log_n`. Only the length is checked here; the result goes through the
+/// usual [`ProofView`] checks when verified.
+pub fn expand_compact_proof<D: ByteBuf>(compact: &D, log_n: usize) -> Result<D, ParseError> {
+    if log_n == 0
+        || log_n > CONST_PROOF_SIZE_LOG_N
+        || compact.len() as usize != compact_proof_bytes(log_n)
+    {
+        return Err(ParseError::WrongLength);
+    }
+    let padding_at = (body_words(log_n) * 32) as u32;
+    let univariate_end = padding_at + (BATCHED_RELATION_PARTIAL_LENGTH * 32) as u32;
+    let pad_univariate = compact.range(padding_at, univariate_end);
+    let pad_fold_comm = compact.range(univariate_end, univariate_end + 4 * 32);
+    let pad_a_evaluation = compact.range(univariate_end + 4 * 32, compact.len());
+
+    let mut out = compact.range(0, 0);
+    let mut cur = 0u32;
+    let words = |out: &mut D, cur: &mut u32, n: usize| {
+        let end = *cur + (n * 32) as u32;
+        out.append(&compact.range(*cur, end));
+        *cur = end;
+    };
+    let points = |out: &mut D, cur: &mut u32, n: usize| {
+        for _ in 0..2 * n {
+            let mut coord = [0u8; 32];
+            compact.read_into(*cur, &mut coord);
+            let (lo, hi) = coord_to_halves_be(&coord);
+            out.extend_from_slice(&lo);
+            out.extend_from_slice(&hi);
+            *cur += 32;
+        }
+    };
+
+    words(&mut out, &mut cur, PAIRING_POINTS_SIZE);
+    points(&mut out, &mut cur, NUMBER_OF_WITNESS_COMMITMENTS);
+    words(&mut out, &mut cur, log_n * BATCHED_RELATION_PARTIAL_LENGTH);
+    for _ in log_n..CONST_PROOF_SIZE_LOG_N {
+        out.append(&pad_univariate);
+    }
+    words(&mut out, &mut cur, NUMBER_OF_ENTITIES);
+    points(&mut out, &mut cur, log_n - 1);
+    for _ in log_n..CONST_PROOF_SIZE_LOG_N {
+        out.append(&pad_fold_comm);
+    }
+    words(&mut out, &mut cur, log_n);
+    for _ in log_n..CONST_PROOF_SIZE_LOG_N {
+        out.append(&pad_a_evaluation);
+    }
+    points(&mut out, &mut cur, 2);
+    Ok(out)
+}
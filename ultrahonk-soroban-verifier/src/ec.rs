@@ -1,8 +1,25 @@
-use crate::{field::Fr, types::G1Point};
-use soroban_sdk::{
-    crypto::bn254::{Bn254G1Affine, Bn254G2Affine, Fr as Bn254Fr},
-    BytesN, Env, Vec,
+use crate::{
+    backend::{Backend, ByteBuf},
+    field::Fr,
+    types::{G1Point, ParseError, PAIRING_POINTS_SIZE},
+    utils::check_g1_point,
 };
+use ark_bn254::Fq;
+use ark_ff::{Field, PrimeField};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec as StdVec;
+#[cfg(feature = "std")]
+use std::vec::Vec as StdVec;
+
+/// BN254 base field modulus q (big-endian).
+pub(crate) const FQ_MODULUS_BE: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x97, 0x81, 0x6a, 0x91, 0x68, 0x71, 0xca, 0x8d, 0x3c, 0x20, 0x8c, 0x16, 0xd8, 0x7c, 0xfd, 0x47,
+];
+
+/// Bits per limb of the pairing point object coordinates.
+const PAIRING_POINT_LIMB_BITS: usize = 68;
 
 const RHS_G2_BYTES: [u8; 128] = [
     0x19, 0x8e, 0x93, 0x93, 0x92, 0x0d, 0x48, 0x3a, 0x72, 0x60, 0xbf, 0xb7, 0x31, 0xfb, 0x5d, 0x25,
@@ -26,68 +43,271 @@ const LHS_G2_BYTES: [u8; 128] = [
     0x11, 0xe6, 0xdd, 0x3f, 0x96, 0xe6, 0xce, 0xa2, 0x85, 0x4a, 0x87, 0xd4, 0xda, 0xcc, 0x5e, 0x55,
 ];
 
-#[inline(always)]
-fn fr_to_bn254(env: &Env, fr: &Fr) -> Bn254Fr {
-    Bn254Fr::from_bytes(BytesN::from_array(env, &fr.to_bytes()))
+/// Whether `pt` satisfies y² = x³ + 3 over Fq. Coordinates must already be
+/// canonical; the point at infinity (0, 0) is not on the curve.
+pub fn g1_is_on_curve(pt: &G1Point) -> bool {
+    let x = Fq::from_be_bytes_mod_order(&pt.x);
+    let y = Fq::from_be_bytes_mod_order(&pt.y);
+    y.square() == x.square() * x + Fq::from(3u64)
 }
 
 #[inline(always)]
-fn g1_from_point(env: &Env, pt: &G1Point) -> Bn254G1Affine {
-    Bn254G1Affine::from_array(env, &pt.to_bytes())
+pub fn rhs_g2_affine<B: Backend>(backend: &B) -> B::G2 {
+    backend.g2(&RHS_G2_BYTES)
 }
 
 #[inline(always)]
-pub fn rhs_g2_affine(env: &Env) -> Bn254G2Affine {
-    Bn254G2Affine::from_array(env, &RHS_G2_BYTES)
+pub fn lhs_g2_affine<B: Backend>(backend: &B) -> B::G2 {
+    backend.g2(&LHS_G2_BYTES)
 }
 
-#[inline(always)]
-pub fn lhs_g2_affine(env: &Env) -> Bn254G2Affine {
-    Bn254G2Affine::from_array(env, &LHS_G2_BYTES)
+/// Bits per digit of the windowed MSM (a divisor of 8).
+const MSM_WINDOW_BITS: usize = 4;
+
+/// How [`g1_msm`] evaluates ∑ sᵢ·Cᵢ with the backend's curve operations.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MsmBackend {
+    /// One `g1_mul` and one `g1_add` per term.
+    PerTerm,
+    /// Interleaved fixed-window (Straus) accumulation using `g1_add` only:
+    /// tables of 1..15·Cᵢ, then per 4-bit window four shared doublings and
+    /// one table lookup per non-zero digit. Trades each `g1_mul` for ~80
+    /// much cheaper additions.
+    #[default]
+    Windowed,
 }
 
 /// Multi-scalar multiplication on G1: ∑ sᵢ·Cᵢ
 #[inline(always)]
-pub fn g1_msm(env: &Env, coms: &[G1Point], scalars: &[Fr]) -> Result<Bn254G1Affine, &'static str> {
+pub fn g1_msm<B: Backend>(
+    backend: &B,
+    msm: MsmBackend,
+    coms: &[G1Point],
+    scalars: &[Fr],
+) -> Result<B::G1, &'static str> {
+    if coms.len() != scalars.len() {
+        return Err("msm len mismatch");
+    }
+    match msm {
+        MsmBackend::PerTerm => msm_per_term(backend, coms, scalars),
+        MsmBackend::Windowed => msm_windowed(backend, coms, scalars),
+    }
+}
+
+/// Compact MSM terms in place: the scalars of a repeated commitment are
+/// summed into its first occurrence, and terms with a zero scalar or the
+/// point at infinity are dropped. The result is `coms[..n]`, `scalars[..n]`.
+pub fn merge_msm_terms(coms: &mut [G1Point], scalars: &mut [Fr]) -> Result<usize, &'static str> {
     if coms.len() != scalars.len() {
         return Err("msm len mismatch");
     }
-    let bn = env.crypto().bn254();
-    let mut acc = Bn254G1Affine::from_array(env, &G1Point::infinity().to_bytes());
+    let infinity = G1Point::infinity();
+    let mut n = 0;
+    for i in 0..coms.len() {
+        let (c, s) = (coms[i], scalars[i]);
+        if s.is_zero() || c == infinity {
+            continue;
+        }
+        match coms[..n].iter().position(|p| *p == c) {
+            Some(j) => scalars[j] = scalars[j] + s,
+            None => {
+                coms[n] = c;
+                scalars[n] = s;
+                n += 1;
+            }
+        }
+    }
+    Ok(n)
+}
+
+fn msm_per_term<B: Backend>(
+    backend: &B,
+    coms: &[G1Point],
+    scalars: &[Fr],
+) -> Result<B::G1, &'static str> {
+    let mut acc = helpers::to_affine(backend, &G1Point::infinity())?;
     for (c, s) in coms.iter().zip(scalars.iter()) {
         if s.is_zero() {
             continue;
         }
-        let p = g1_from_point(env, c);
-        let scalar = fr_to_bn254(env, s);
-        let term = bn.g1_mul(&p, &scalar);
-        acc = bn.g1_add(&acc, &term);
+        let term = backend.g1_mul(&helpers::to_affine(backend, c)?, s);
+        acc = backend.g1_add(&acc, &term);
     }
     Ok(acc)
 }
 
+/// `MSM_WINDOW_BITS`-bit digit `w` of `s`, counting from the least
+/// significant end.
+#[inline(always)]
+fn window_digit(s: &[u8; 32], w: usize) -> usize {
+    let byte = s[31 - w * MSM_WINDOW_BITS / 8];
+    ((byte >> ((w * MSM_WINDOW_BITS) % 8)) & ((1 << MSM_WINDOW_BITS) - 1)) as usize
+}
+
+fn msm_windowed<B: Backend>(
+    backend: &B,
+    coms: &[G1Point],
+    scalars: &[Fr],
+) -> Result<B::G1, &'static str> {
+    const WINDOWS: usize = 256 / MSM_WINDOW_BITS;
+
+    let mut terms = StdVec::with_capacity(coms.len());
+    for (c, s) in coms.iter().zip(scalars.iter()) {
+        if !s.is_zero() {
+            terms.push((s.to_bytes(), helpers::to_affine(backend, c)?));
+        }
+    }
+
+    // tables[i][d - 1] = d·Cᵢ, extended on first use of digit d
+    let mut tables: StdVec<StdVec<B::G1>> = StdVec::with_capacity(terms.len());
+    tables.resize_with(terms.len(), StdVec::new);
+
+    let mut acc: Option<B::G1> = None;
+    for w in (0..WINDOWS).rev() {
+        if let Some(a) = acc.as_mut() {
+            for _ in 0..MSM_WINDOW_BITS {
+                *a = backend.g1_add(a, a);
+            }
+        }
+        for ((s, p), table) in terms.iter().zip(tables.iter_mut()) {
+            let d = window_digit(s, w);
+            if d == 0 {
+                continue;
+            }
+            while table.len() < d {
+                let next = match table.last() {
+                    None => p.clone(),
+                    Some(last) => backend.g1_add(last, p),
+                };
+                table.push(next);
+            }
+            let t = &table[d - 1];
+            acc = Some(match acc {
+                None => t.clone(),
+                Some(a) => backend.g1_add(&a, t),
+            });
+        }
+    }
+    match acc {
+        Some(a) => Ok(a),
+        None => helpers::to_affine(backend, &G1Point::infinity()),
+    }
+}
+
 /// Pairing product check e(P0, rhs_g2) * e(P1, lhs_g2) == 1
 #[inline(always)]
-pub fn pairing_check(env: &Env, p0: &Bn254G1Affine, p1: &Bn254G1Affine) -> bool {
-    let mut g1s: Vec<Bn254G1Affine> = Vec::new(env);
-    g1s.push_back(p0.clone());
-    g1s.push_back(p1.clone());
-    let mut g2s: Vec<Bn254G2Affine> = Vec::new(env);
-    g2s.push_back(rhs_g2_affine(env));
-    g2s.push_back(lhs_g2_affine(env));
-    env.crypto().bn254().pairing_check(g1s, g2s)
+pub fn pairing_check<B: Backend>(backend: &B, p0: &B::G1, p1: &B::G1) -> bool {
+    backend.pairing_check(
+        &[p0.clone(), p1.clone()],
+        &[rhs_g2_affine(backend), lhs_g2_affine(backend)],
+    )
+}
+
+/// Settle several pairing products e(P0ᵢ, rhs_g2)·e(P1ᵢ, lhs_g2) == 1 with
+/// one pairing: both G1 sides are folded with weights 1, ρ, ρ², … first.
+/// `rho` must be derived from all the points being folded.
+pub fn batch_pairing_check<B: Backend>(backend: &B, points: &[(B::G1, B::G1)], rho: &Fr) -> bool {
+    let Some(((first0, first1), rest)) = points.split_first() else {
+        return false;
+    };
+    let (mut acc0, mut acc1) = (first0.clone(), first1.clone());
+    let mut weight = *rho;
+    for (p0, p1) in rest {
+        acc0 = backend.g1_add(&acc0, &backend.g1_mul(p0, &weight));
+        acc1 = backend.g1_add(&acc1, &backend.g1_mul(p1, &weight));
+        weight = weight * *rho;
+    }
+    pairing_check(backend, &acc0, &acc1)
+}
+
+/// Recombine four 68-bit limbs (least significant first) into a 32-byte
+/// big-endian base field coordinate.
+pub(crate) fn limbs_to_coord(limbs: &[Fr]) -> Result<[u8; 32], &'static str> {
+    // little-endian 64-bit words; 4 × 68 bits spill into a fifth
+    let mut acc = [0u64; 5];
+    for (i, limb) in limbs.iter().enumerate() {
+        let b = limb.to_bytes();
+        if b[..23].iter().any(|&x| x != 0) || b[23] >= 0x10 {
+            return Err("bigfield limb too large");
+        }
+        let mut lo = [0u8; 16];
+        lo.copy_from_slice(&b[16..]);
+        let v = u128::from_be_bytes(lo);
+        let shift = PAIRING_POINT_LIMB_BITS * i;
+        let (word, bit) = (shift / 64, shift % 64);
+        for (k, part) in [v as u64, (v >> 64) as u64].into_iter().enumerate() {
+            acc[word + k] |= part << bit;
+            if bit != 0 && word + k + 1 < acc.len() {
+                acc[word + k + 1] |= part >> (64 - bit);
+            }
+        }
+    }
+    if acc[4] != 0 {
+        return Err("bigfield value overflow");
+    }
+    let mut out = [0u8; 32];
+    for (i, w) in acc[..4].iter().enumerate() {
+        out[24 - 8 * i..32 - 8 * i].copy_from_slice(&w.to_be_bytes());
+    }
+    if out >= FQ_MODULUS_BE {
+        return Err("bigfield value not in Fq");
+    }
+    Ok(out)
+}
+
+/// Recombine the pairing point object into the (lhs, rhs) G1 accumulator
+/// left by the recursive verifications inside the circuit. Both points must
+/// be on the curve (or at infinity).
+pub fn pairing_points_to_g1(
+    ppo: &[Fr; PAIRING_POINTS_SIZE],
+) -> Result<(G1Point, G1Point), ParseError> {
+    let coord =
+        |limbs: &[Fr]| limbs_to_coord(limbs).map_err(|_| ParseError::NonCanonicalCoordinate);
+    let lhs = check_g1_point(coord(&ppo[0..4])?, coord(&ppo[4..8])?)?;
+    let rhs = check_g1_point(coord(&ppo[8..12])?, coord(&ppo[12..16])?)?;
+    Ok((lhs, rhs))
+}
+
+/// Fold the recursion accumulator into the KZG pairing inputs with a
+/// Keccak recursion separator s = H(lhs ‖ rhs ‖ P0 ‖ P1):
+/// (P0, P1) ← (s·P0 + lhs, s·P1 + rhs).
+pub fn aggregate_pairing_points<B: Backend>(
+    backend: &B,
+    p0: &B::G1,
+    p1: &B::G1,
+    ppo: &[Fr; PAIRING_POINTS_SIZE],
+) -> Result<(B::G1, B::G1), &'static str> {
+    let (lhs, rhs) = pairing_points_to_g1(ppo).map_err(|_| "invalid pairing point object")?;
+
+    let mut data = backend.bytes();
+    data.extend_from_slice(&lhs.to_bytes());
+    data.extend_from_slice(&rhs.to_bytes());
+    data.extend_from_slice(&backend.g1_to_bytes(p0));
+    data.extend_from_slice(&backend.g1_to_bytes(p1));
+    let separator = Fr::from_bytes(&backend.keccak256(&data));
+
+    let p0 = backend.g1_add(
+        &backend.g1_mul(p0, &separator),
+        &helpers::to_affine(backend, &lhs)?,
+    );
+    let p1 = backend.g1_add(
+        &backend.g1_mul(p1, &separator),
+        &helpers::to_affine(backend, &rhs)?,
+    );
+    Ok((p0, p1))
 }
 
 pub mod helpers {
     use super::*;
 
+    /// `pt` in the backend's representation; fails off the curve.
     #[inline(always)]
-    pub fn to_affine(env: &Env, pt: &G1Point) -> Bn254G1Affine {
-        g1_from_point(env, pt)
+    pub fn to_affine<B: Backend>(backend: &B, pt: &G1Point) -> Result<B::G1, &'static str> {
+        backend.g1(pt).map_err(|_| "point not on curve")
     }
 
     #[inline(always)]
-    pub fn negate(env: &Env, pt: &G1Point) -> Bn254G1Affine {
-        -g1_from_point(env, pt)
+    pub fn negate<B: Backend>(backend: &B, pt: &G1Point) -> Result<B::G1, &'static str> {
+        Ok(backend.g1_neg(&to_affine(backend, pt)?))
     }
 }
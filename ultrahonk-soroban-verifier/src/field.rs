@@ -1,6 +1,7 @@
+use crate::types::ParseError;
 use ark_bn254::Fr as ArkFr;
 use ark_ff::BigInteger256;
-use ark_ff::{Field, PrimeField, Zero};
+use ark_ff::{AdditiveGroup, Field, PrimeField, Zero};
 use core::ops::{Add, Mul, Neg, Sub};
 use hex;
 
@@ -20,23 +21,111 @@ fn normalize_hex(s: &str) -> String {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct Fr(pub ArkFr);
 
+/// Build an element from canonical little-endian limbs. `ArkFr::new` does
+/// the Montgomery conversion, so in a `const` it happens at compile time.
+const fn from_canonical_limbs(limbs: [u64; 4]) -> Fr {
+    let modulus = <ArkFr as PrimeField>::MODULUS.0;
+    let mut i = 4;
+    while i > 0 {
+        i -= 1;
+        if limbs[i] < modulus[i] {
+            return Fr(ArkFr::new(BigInteger256::new(limbs)));
+        }
+        assert!(limbs[i] == modulus[i], "Fr constant not below the modulus");
+    }
+    panic!("Fr constant not below the modulus")
+}
+
+/// r as 32 big-endian bytes, for checking raw words without decoding them.
+pub(crate) const MODULUS_BE: [u8; 32] = {
+    let modulus = <ArkFr as PrimeField>::MODULUS.0;
+    let mut out = [0u8; 32];
+    let mut i = 0;
+    while i < 32 {
+        out[31 - i] = (modulus[i / 8] >> (8 * (i % 8))) as u8;
+        i += 1;
+    }
+    out
+};
+
+/// Reduce a 32-byte big-endian integer mod r without leaving byte form:
+/// 2^256 < 6r, so at most five subtractions.
+pub(crate) fn reduce_be(bytes: &[u8; 32]) -> [u8; 32] {
+    let mut out = *bytes;
+    while out >= MODULUS_BE {
+        let mut borrow = 0u16;
+        for i in (0..32).rev() {
+            let diff = 0x100 + out[i] as u16 - MODULUS_BE[i] as u16 - borrow;
+            out[i] = diff as u8;
+            borrow = 1 - (diff >> 8);
+        }
+    }
+    out
+}
+
 impl Fr {
     /// Construct from u64.
-    pub fn from_u64(x: u64) -> Self {
-        Fr(ArkFr::from(x))
+    pub const fn from_u64(x: u64) -> Self {
+        Fr(ArkFr::new(BigInteger256::new([x, 0, 0, 0])))
+    }
+
+    /// Construct from u128.
+    pub const fn from_u128(x: u128) -> Self {
+        Fr(ArkFr::new(BigInteger256::new([
+            x as u64,
+            (x >> 64) as u64,
+            0,
+            0,
+        ])))
+    }
+
+    /// Const counterpart of [`Fr::from_str`] for `const` items: the hex
+    /// literal is parsed and put in Montgomery form at compile time, and a
+    /// malformed or non-canonical literal fails the build.
+    pub const fn from_str_const(s: &str) -> Self {
+        let digits = s.as_bytes();
+        let start = if digits.len() >= 2 && digits[0] == b'0' && digits[1] == b'x' {
+            2
+        } else {
+            0
+        };
+        assert!(digits.len() - start <= 64, "Fr hex constant too long");
+        let mut limbs = [0u64; 4];
+        let mut k = 0;
+        while k < digits.len() - start {
+            let nibble = match digits[digits.len() - 1 - k] {
+                c @ b'0'..=b'9' => c - b'0',
+                c @ b'a'..=b'f' => c - b'a' + 10,
+                c @ b'A'..=b'F' => c - b'A' + 10,
+                _ => panic!("invalid Fr hex constant"),
+            };
+            limbs[k / 16] |= (nibble as u64) << (4 * (k % 16));
+            k += 1;
+        }
+        from_canonical_limbs(limbs)
     }
 
-    /// Construct from hex string (with or without 0x prefix).
-    /// Normalize to even digits before `hex::decode` so OddLength exception won't occur.
+    /// Construct from a hardcoded hex constant (with or without 0x prefix).
+    /// Panics on malformed input; use [`Fr::try_from_str`] for anything
+    /// that is not a literal in this crate.
     pub fn from_str(s: &str) -> Self {
-        let bytes = hex::decode(normalize_hex(s)).expect("hex decode failed");
+        Self::try_from_str(s).expect("invalid Fr hex constant")
+    }
+
+    /// Parse a hex string (with or without 0x prefix) holding a canonical
+    /// field element. Odd digit counts are left-padded with a zero.
+    pub fn try_from_str(s: &str) -> Result<Self, ParseError> {
+        let bytes = hex::decode(normalize_hex(s)).map_err(|_| ParseError::NonCanonicalScalar)?;
+        if bytes.len() > 32 {
+            return Err(ParseError::NonCanonicalScalar);
+        }
         let mut padded = [0u8; 32];
         let offset = 32 - bytes.len();
         padded[offset..].copy_from_slice(&bytes);
-        Self::from_bytes(&padded)
+        Self::from_bytes_canonical(&padded).ok_or(ParseError::NonCanonicalScalar)
     }
 
     /// Construct from a 32-byte big-endian array.
@@ -47,6 +136,18 @@ impl Fr {
         Fr(ArkFr::from_le_bytes_mod_order(&tmp))
     }
 
+    /// Construct from a 32-byte big-endian array, rejecting values ≥ r
+    /// instead of reducing them.
+    pub fn from_bytes_canonical(bytes: &[u8; 32]) -> Option<Self> {
+        let mut limbs = [0u64; 4];
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(&bytes[24 - 8 * i..32 - 8 * i]);
+            *limb = u64::from_be_bytes(word);
+        }
+        ArkFr::from_bigint(BigInteger256::new(limbs)).map(Fr)
+    }
+
     /// Convert to 32-byte big-endian representation.
     #[inline(always)]
     pub fn to_bytes(&self) -> [u8; 32] {
@@ -62,20 +163,34 @@ impl Fr {
         self.0.inverse().map(Fr)
     }
 
-    pub fn zero() -> Self {
-        Fr(ArkFr::zero())
+    pub const fn zero() -> Self {
+        Fr(ArkFr::ZERO)
     }
 
-    pub fn one() -> Self {
+    pub const fn one() -> Self {
         Fr(ArkFr::ONE)
     }
 
+    #[cfg(not(feature = "fr32"))]
     pub fn pow(&self, exp: u128) -> Self {
         let mut bits = [0u64; 4];
         bits[0] = exp as u64;
         Fr(self.0.pow(bits))
     }
 
+    #[cfg(feature = "fr32")]
+    pub fn pow(&self, exp: u128) -> Self {
+        let exp = exp as u64;
+        let mut acc = Fr::one();
+        for i in (0..64 - exp.leading_zeros()).rev() {
+            acc = acc * acc;
+            if (exp >> i) & 1 == 1 {
+                acc = acc * *self;
+            }
+        }
+        acc
+    }
+
     pub fn is_zero(&self) -> bool {
         self.0.is_zero()
     }
@@ -113,25 +228,175 @@ pub fn batch_inverse(vals: &[Fr], out: &mut [Fr]) -> Result<(), &'static str> {
     Ok(())
 }
 
+/// Fr arithmetic on 32-bit limbs (`fr32` feature).
+///
+/// ark-ff multiplies 4×64-bit limbs, and every 64×64→128 product is emulated
+/// on `wasm32`. Here the same Montgomery representation (R = 2^256) is read as
+/// 8×32-bit limbs, so each partial product is one native 32×32→64 multiply.
+/// Results are bit-identical to ark's, which keeps `Fr(pub ArkFr)` as is.
+#[cfg(feature = "fr32")]
+mod mont32 {
+    use super::ArkFr;
+    use ark_ff::{BigInteger256, PrimeField};
+
+    const N: usize = 8;
+
+    const fn split(x: [u64; 4]) -> [u32; N] {
+        let mut out = [0u32; N];
+        let mut i = 0;
+        while i < 4 {
+            out[2 * i] = x[i] as u32;
+            out[2 * i + 1] = (x[i] >> 32) as u32;
+            i += 1;
+        }
+        out
+    }
+
+    const MODULUS: [u32; N] = split(<ArkFr as PrimeField>::MODULUS.0);
+
+    /// -r⁻¹ mod 2^32, by Newton iteration on the lowest limb.
+    const INV: u32 = {
+        let mut x = 1u32;
+        let mut i = 0;
+        while i < 5 {
+            x = x.wrapping_mul(2u32.wrapping_sub(MODULUS[0].wrapping_mul(x)));
+            i += 1;
+        }
+        x.wrapping_neg()
+    };
+
+    #[inline(always)]
+    fn limbs(a: &ArkFr) -> [u32; N] {
+        split(a.0 .0)
+    }
+
+    #[inline(always)]
+    fn element(x: &[u32; N]) -> ArkFr {
+        let mut out = [0u64; 4];
+        for (i, w) in out.iter_mut().enumerate() {
+            *w = x[2 * i] as u64 | (x[2 * i + 1] as u64) << 32;
+        }
+        ArkFr::new_unchecked(BigInteger256::new(out))
+    }
+
+    /// x - r if x ≥ r; `x` must be below 2r.
+    #[inline(always)]
+    fn reduce(x: &mut [u32; N]) {
+        let mut diff = [0u32; N];
+        let mut borrow = 0u64;
+        for i in 0..N {
+            let d = (x[i] as u64).wrapping_sub(MODULUS[i] as u64 + borrow);
+            diff[i] = d as u32;
+            borrow = d >> 63;
+        }
+        if borrow == 0 {
+            *x = diff;
+        }
+    }
+
+    /// Montgomery product a·b·R⁻¹ (CIOS). r < 2^254, so the running value
+    /// fits N limbs plus one carry word.
+    #[inline(always)]
+    pub fn mul(a: &ArkFr, b: &ArkFr) -> ArkFr {
+        let (a, b) = (limbs(a), limbs(b));
+        let mut t = [0u32; N + 1];
+        for &bi in &b {
+            let mut carry = 0u64;
+            for j in 0..N {
+                let s = t[j] as u64 + a[j] as u64 * bi as u64 + carry;
+                t[j] = s as u32;
+                carry = s >> 32;
+            }
+            let top = t[N] as u64 + carry;
+
+            let m = t[0].wrapping_mul(INV) as u64;
+            let mut carry = (t[0] as u64 + m * MODULUS[0] as u64) >> 32;
+            for j in 1..N {
+                let s = t[j] as u64 + m * MODULUS[j] as u64 + carry;
+                t[j - 1] = s as u32;
+                carry = s >> 32;
+            }
+            let s = top + carry;
+            t[N - 1] = s as u32;
+            t[N] = (s >> 32) as u32;
+        }
+        let mut out = [0u32; N];
+        out.copy_from_slice(&t[..N]);
+        reduce(&mut out);
+        element(&out)
+    }
+
+    #[inline(always)]
+    pub fn add(a: &ArkFr, b: &ArkFr) -> ArkFr {
+        let (a, b) = (limbs(a), limbs(b));
+        let mut out = [0u32; N];
+        let mut carry = 0u64;
+        for i in 0..N {
+            let s = a[i] as u64 + b[i] as u64 + carry;
+            out[i] = s as u32;
+            carry = s >> 32;
+        }
+        // a + b < 2r < 2^256, no carry out
+        reduce(&mut out);
+        element(&out)
+    }
+
+    #[inline(always)]
+    pub fn sub(a: &ArkFr, b: &ArkFr) -> ArkFr {
+        let (a, b) = (limbs(a), limbs(b));
+        let mut out = [0u32; N];
+        let mut borrow = 0u64;
+        for i in 0..N {
+            let d = (a[i] as u64).wrapping_sub(b[i] as u64 + borrow);
+            out[i] = d as u32;
+            borrow = d >> 63;
+        }
+        if borrow != 0 {
+            let mut carry = 0u64;
+            for i in 0..N {
+                let s = out[i] as u64 + MODULUS[i] as u64 + carry;
+                out[i] = s as u32;
+                carry = s >> 32;
+            }
+        }
+        element(&out)
+    }
+}
+
 impl Add for Fr {
     type Output = Fr;
+    #[cfg(not(feature = "fr32"))]
     fn add(self, rhs: Fr) -> Fr {
         Fr(self.0 + rhs.0)
     }
+    #[cfg(feature = "fr32")]
+    fn add(self, rhs: Fr) -> Fr {
+        Fr(mont32::add(&self.0, &rhs.0))
+    }
 }
 
 impl Sub for Fr {
     type Output = Fr;
+    #[cfg(not(feature = "fr32"))]
     fn sub(self, rhs: Fr) -> Fr {
         Fr(self.0 - rhs.0)
     }
+    #[cfg(feature = "fr32")]
+    fn sub(self, rhs: Fr) -> Fr {
+        Fr(mont32::sub(&self.0, &rhs.0))
+    }
 }
 
 impl Mul for Fr {
     type Output = Fr;
+    #[cfg(not(feature = "fr32"))]
     fn mul(self, rhs: Fr) -> Fr {
         Fr(self.0 * rhs.0)
     }
+    #[cfg(feature = "fr32")]
+    fn mul(self, rhs: Fr) -> Fr {
+        Fr(mont32::mul(&self.0, &rhs.0))
+    }
 }
 
 impl Neg for Fr {
@@ -0,0 +1,181 @@
+//! Honk flavors: entity counts, commitment order and relation set.
+//!
+//! The proof layout ([`crate::view::ProofView`], [`crate::format::ProofFormat`]),
+//! the transcript, sum-check, the relation accumulator and Shplemini take
+//! their per-flavor parameters from a [`Flavor`]. The constants in
+//! [`crate::types`] are those of the Ultra flavors and size the verifier's
+//! fixed buffers, so a flavor's counts must not exceed them; this is checked
+//! at compile time for the flavors below.
+
+use crate::field::Fr;
+use crate::format::{NUMBER_OF_WITNESS_COMMITMENTS, VK_NUM_POINTS};
+use crate::relations::{Relation, ULTRA_RELATIONS};
+use crate::types::{
+    Wire, BATCHED_RELATION_PARTIAL_LENGTH, NUMBER_OF_ALPHAS, NUMBER_OF_ENTITIES,
+    NUMBER_OF_SUBRELATIONS, NUMBER_TO_BE_SHIFTED, NUMBER_UNSHIFTED,
+    ZK_BATCHED_RELATION_PARTIAL_LENGTH,
+};
+use crate::view::WitnessCommitment;
+
+pub trait Flavor {
+    /// Libra-masked (`bb prove --zk`): the proof carries the Libra and
+    /// Gemini masking claims.
+    const ZK: bool;
+    /// Polynomials evaluated at the sum-check point: precomputed, witness,
+    /// then shifted witness.
+    const NUMBER_OF_ENTITIES: usize;
+    const NUMBER_UNSHIFTED: usize;
+    const NUMBER_TO_BE_SHIFTED: usize;
+    const NUMBER_OF_SUBRELATIONS: usize;
+    /// Challenges batching the subrelations after the first.
+    const NUMBER_OF_ALPHAS: usize;
+    /// Coefficients per sum-check round univariate.
+    const BATCHED_RELATION_PARTIAL_LENGTH: usize;
+
+    /// One round univariate, `[Fr; BATCHED_RELATION_PARTIAL_LENGTH]`.
+    type Univariate: Copy + Default + AsRef<[Fr]> + AsMut<[Fr]>;
+    /// Barycentric denominators ∏_{j≠i} (i - j) over the evaluation domain
+    /// 0..BATCHED_RELATION_PARTIAL_LENGTH.
+    const BARYCENTRIC: Self::Univariate;
+
+    /// VK commitments in Shplemini batching order.
+    const PRECOMPUTED: &'static [Wire];
+    /// Witness commitments in Shplemini batching order, after the VK ones.
+    const WITNESS: &'static [WitnessCommitment];
+    /// Witness commitments opened again at the shifted point, in order.
+    const SHIFTED: &'static [WitnessCommitment];
+
+    /// Relations accumulated into the subrelation evaluations, in order.
+    const RELATIONS: &'static [Relation];
+}
+
+/// UltraHonk, as proven by `bb prove` (also the Honk part of
+/// UltraRollupHonk).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct UltraFlavor;
+
+/// UltraHonk with Libra masking (`bb prove --zk`): one more coefficient per
+/// round univariate.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct UltraZkFlavor;
+
+/// Matches the Solidity verifier's commitment order.
+const ULTRA_PRECOMPUTED: [Wire; VK_NUM_POINTS] = [
+    Wire::Qm,
+    Wire::Qc,
+    Wire::Ql,
+    Wire::Qr,
+    Wire::Qo,
+    Wire::Q4,
+    Wire::QLookup,
+    Wire::QArith,
+    Wire::QRange,
+    Wire::QElliptic,
+    Wire::QAux,
+    Wire::QPoseidon2External,
+    Wire::QPoseidon2Internal,
+    Wire::Sigma1,
+    Wire::Sigma2,
+    Wire::Sigma3,
+    Wire::Sigma4,
+    Wire::Id1,
+    Wire::Id2,
+    Wire::Id3,
+    Wire::Id4,
+    Wire::Table1,
+    Wire::Table2,
+    Wire::Table3,
+    Wire::Table4,
+    Wire::LagrangeFirst,
+    Wire::LagrangeLast,
+];
+
+const ULTRA_WITNESS: [WitnessCommitment; NUMBER_OF_WITNESS_COMMITMENTS] = [
+    WitnessCommitment::W1,
+    WitnessCommitment::W2,
+    WitnessCommitment::W3,
+    WitnessCommitment::W4,
+    WitnessCommitment::ZPerm,
+    WitnessCommitment::LookupInverses,
+    WitnessCommitment::LookupReadCounts,
+    WitnessCommitment::LookupReadTags,
+];
+
+const ULTRA_SHIFTED: [WitnessCommitment; NUMBER_TO_BE_SHIFTED] = [
+    WitnessCommitment::W1,
+    WitnessCommitment::W2,
+    WitnessCommitment::W3,
+    WitnessCommitment::W4,
+    WitnessCommitment::ZPerm,
+];
+
+impl Flavor for UltraFlavor {
+    const ZK: bool = false;
+    const NUMBER_OF_ENTITIES: usize = NUMBER_OF_ENTITIES;
+    const NUMBER_UNSHIFTED: usize = NUMBER_UNSHIFTED;
+    const NUMBER_TO_BE_SHIFTED: usize = NUMBER_TO_BE_SHIFTED;
+    const NUMBER_OF_SUBRELATIONS: usize = NUMBER_OF_SUBRELATIONS;
+    const NUMBER_OF_ALPHAS: usize = NUMBER_OF_ALPHAS;
+    const BATCHED_RELATION_PARTIAL_LENGTH: usize = BATCHED_RELATION_PARTIAL_LENGTH;
+
+    type Univariate = [Fr; BATCHED_RELATION_PARTIAL_LENGTH];
+    const BARYCENTRIC: Self::Univariate = [
+        Fr::from_str_const("0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593efffec51"),
+        Fr::from_str_const("0x00000000000000000000000000000000000000000000000000000000000002d0"),
+        Fr::from_str_const("0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593efffff11"),
+        Fr::from_str_const("0x0000000000000000000000000000000000000000000000000000000000000090"),
+        Fr::from_str_const("0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593efffff71"),
+        Fr::from_str_const("0x00000000000000000000000000000000000000000000000000000000000000f0"),
+        Fr::from_str_const("0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593effffd31"),
+        Fr::from_str_const("0x00000000000000000000000000000000000000000000000000000000000013b0"),
+    ];
+
+    const PRECOMPUTED: &'static [Wire] = &ULTRA_PRECOMPUTED;
+    const WITNESS: &'static [WitnessCommitment] = &ULTRA_WITNESS;
+    const SHIFTED: &'static [WitnessCommitment] = &ULTRA_SHIFTED;
+
+    const RELATIONS: &'static [Relation] = &ULTRA_RELATIONS;
+}
+
+impl Flavor for UltraZkFlavor {
+    const ZK: bool = true;
+    const NUMBER_OF_ENTITIES: usize = UltraFlavor::NUMBER_OF_ENTITIES;
+    const NUMBER_UNSHIFTED: usize = UltraFlavor::NUMBER_UNSHIFTED;
+    const NUMBER_TO_BE_SHIFTED: usize = UltraFlavor::NUMBER_TO_BE_SHIFTED;
+    const NUMBER_OF_SUBRELATIONS: usize = UltraFlavor::NUMBER_OF_SUBRELATIONS;
+    const NUMBER_OF_ALPHAS: usize = UltraFlavor::NUMBER_OF_ALPHAS;
+    const BATCHED_RELATION_PARTIAL_LENGTH: usize = ZK_BATCHED_RELATION_PARTIAL_LENGTH;
+
+    type Univariate = [Fr; ZK_BATCHED_RELATION_PARTIAL_LENGTH];
+    const BARYCENTRIC: Self::Univariate = [
+        Fr::from_str_const("0x0000000000000000000000000000000000000000000000000000000000009d80"),
+        Fr::from_str_const("0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593efffec51"),
+        Fr::from_str_const("0x00000000000000000000000000000000000000000000000000000000000005a0"),
+        Fr::from_str_const("0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593effffd31"),
+        Fr::from_str_const("0x0000000000000000000000000000000000000000000000000000000000000240"),
+        Fr::from_str_const("0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593effffd31"),
+        Fr::from_str_const("0x00000000000000000000000000000000000000000000000000000000000005a0"),
+        Fr::from_str_const("0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593efffec51"),
+        Fr::from_str_const("0x0000000000000000000000000000000000000000000000000000000000009d80"),
+    ];
+
+    const PRECOMPUTED: &'static [Wire] = UltraFlavor::PRECOMPUTED;
+    const WITNESS: &'static [WitnessCommitment] = UltraFlavor::WITNESS;
+    const SHIFTED: &'static [WitnessCommitment] = UltraFlavor::SHIFTED;
+
+    const RELATIONS: &'static [Relation] = UltraFlavor::RELATIONS;
+}
+
+/// `F`'s counts are consistent and fit the buffers sized in [`crate::types`].
+const fn fits<F: Flavor>() -> bool {
+    F::NUMBER_OF_ENTITIES == F::PRECOMPUTED.len() + F::WITNESS.len() + F::SHIFTED.len()
+        && F::NUMBER_OF_ENTITIES == F::NUMBER_UNSHIFTED + F::NUMBER_TO_BE_SHIFTED
+        && F::NUMBER_TO_BE_SHIFTED == F::SHIFTED.len()
+        && F::NUMBER_OF_ALPHAS + 1 == F::NUMBER_OF_SUBRELATIONS
+        && F::NUMBER_OF_ENTITIES <= NUMBER_OF_ENTITIES
+        && F::NUMBER_OF_SUBRELATIONS <= NUMBER_OF_SUBRELATIONS
+        && F::PRECOMPUTED.len() <= VK_NUM_POINTS
+        && F::WITNESS.len() <= NUMBER_OF_WITNESS_COMMITMENTS
+}
+
+const _: () = assert!(fits::<UltraFlavor>() && fits::<UltraZkFlavor>());
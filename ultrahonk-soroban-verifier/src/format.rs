@@ -0,0 +1,158 @@
+//! bb proof / VK serialization layouts and their detection.
+//!
+//! Only bb v0.87.0 is supported. Another release's layout belongs here once
+//! its artifacts are checked in alongside the ones under `circuits/`.
+
+use crate::backend::ByteBuf;
+use crate::flavor::{Flavor, UltraFlavor, UltraZkFlavor};
+use crate::types::{
+    Wire, CONST_PROOF_SIZE_LOG_N, LIBRA_COMMITMENTS, LIBRA_EVALUATIONS, PAIRING_POINTS_SIZE,
+};
+
+/// w1..w4, lookup_read_counts, lookup_read_tags, lookup_inverses, z_perm.
+pub(crate) const NUMBER_OF_WITNESS_COMMITMENTS: usize = 8;
+/// Precomputed commitments carried by every VK layout.
+pub const VK_NUM_POINTS: usize = 27;
+
+/// bb v0.87.0 VK commitment order.
+const V0_87_VK_COMMITMENTS: [Wire; VK_NUM_POINTS] = [
+    Wire::Qm,
+    Wire::Qc,
+    Wire::Ql,
+    Wire::Qr,
+    Wire::Qo,
+    Wire::Q4,
+    Wire::QLookup,
+    Wire::QArith,
+    Wire::QRange,
+    Wire::QElliptic,
+    Wire::QAux,
+    Wire::QPoseidon2External,
+    Wire::QPoseidon2Internal,
+    Wire::Sigma1,
+    Wire::Sigma2,
+    Wire::Sigma3,
+    Wire::Sigma4,
+    Wire::Id1,
+    Wire::Id2,
+    Wire::Id3,
+    Wire::Id4,
+    Wire::Table1,
+    Wire::Table2,
+    Wire::Table3,
+    Wire::Table4,
+    Wire::LagrangeFirst,
+    Wire::LagrangeLast,
+];
+
+/// Proof serialization layout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProofFormat {
+    /// bb v0.87.0: G1 coordinates split into (lo136, hi) limbs, sumcheck and
+    /// Gemini rounds padded to `CONST_PROOF_SIZE_LOG_N`.
+    V0_87,
+}
+
+impl ProofFormat {
+    pub const ALL: [ProofFormat; 1] = [ProofFormat::V0_87];
+
+    /// 32-byte words per G1 commitment.
+    pub const fn point_words(self) -> usize {
+        match self {
+            ProofFormat::V0_87 => 4,
+        }
+    }
+
+    /// Sumcheck / Gemini rounds serialized for a circuit of size 2^`log_n`.
+    pub const fn rounds(self, _log_n: usize) -> usize {
+        match self {
+            ProofFormat::V0_87 => CONST_PROOF_SIZE_LOG_N,
+        }
+    }
+
+    /// Length in 32-byte words of an `F` proof.
+    pub const fn proof_words<F: Flavor>(self, log_n: usize) -> usize {
+        let p = self.point_words();
+        let r = self.rounds(log_n);
+        // pairing points, witness commitments, sumcheck univariates and
+        // evaluations, Gemini folds and a-evaluations, shplonk_q and
+        // kzg_quotient
+        let words = PAIRING_POINTS_SIZE
+            + F::WITNESS.len() * p
+            + r * F::BATCHED_RELATION_PARTIAL_LENGTH
+            + F::NUMBER_OF_ENTITIES
+            + (r - 1) * p
+            + r
+            + 2 * p;
+        if F::ZK {
+            // libra commitments + sum + evaluation, masking poly + eval,
+            // libra poly evals
+            words + LIBRA_COMMITMENTS * p + 2 + p + 1 + LIBRA_EVALUATIONS
+        } else {
+            words
+        }
+    }
+
+    /// Length in bytes of an `F` proof.
+    pub const fn proof_bytes<F: Flavor>(self, log_n: usize) -> usize {
+        self.proof_words::<F>(log_n) * 32
+    }
+
+    /// Identify the layout (and ZK flag) of a proof of `len` bytes for a
+    /// circuit with `log_n` rounds.
+    pub fn detect(len: usize, log_n: usize) -> Option<(ProofFormat, bool)> {
+        if log_n == 0 || log_n > CONST_PROOF_SIZE_LOG_N {
+            return None;
+        }
+        for format in Self::ALL {
+            if format.proof_bytes::<UltraFlavor>(log_n) == len {
+                return Some((format, false));
+            }
+            if format.proof_bytes::<UltraZkFlavor>(log_n) == len {
+                return Some((format, true));
+            }
+        }
+        None
+    }
+
+    /// Whether `len` matches any supported layout for some circuit size.
+    /// Used by callers that do not hold the VK.
+    pub fn is_known_len(len: usize) -> bool {
+        (1..=CONST_PROOF_SIZE_LOG_N).any(|log_n| Self::detect(len, log_n).is_some())
+    }
+}
+
+/// Verification key serialization layout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VkFormat {
+    /// bb v0.87.0: 4×u64 header (circuit_size, log_circuit_size,
+    /// public_inputs_size, pub_inputs_offset), then 27 (x, y) points.
+    V0_87,
+}
+
+impl VkFormat {
+    pub const ALL: [VkFormat; 1] = [VkFormat::V0_87];
+
+    pub const fn header_bytes(self) -> usize {
+        match self {
+            VkFormat::V0_87 => 4 * 8,
+        }
+    }
+
+    /// Precomputed commitments in serialization order.
+    pub const fn commitments(self) -> &'static [Wire] {
+        match self {
+            VkFormat::V0_87 => &V0_87_VK_COMMITMENTS,
+        }
+    }
+
+    pub const fn vk_bytes(self) -> usize {
+        self.header_bytes() + self.commitments().len() * 64
+    }
+
+    /// Identify the layout from the length.
+    pub fn detect(bytes: &impl ByteBuf) -> Option<VkFormat> {
+        let len = bytes.len() as usize;
+        Self::ALL.into_iter().find(|f| f.vk_bytes() == len)
+    }
+}
@@ -0,0 +1,243 @@
+//! Grumpkin curve arithmetic: y² = x³ − 17 over the BN254 scalar field.
+//!
+//! Coordinates live in [`Fr`]; scalars live in the Grumpkin scalar field,
+//! which is the BN254 base field (`ark_bn254::Fq`).
+
+use crate::field::Fr;
+use ark_bn254::Fq;
+use ark_ff::PrimeField;
+use core::ops::{Add, Neg};
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+/// Affine Grumpkin point; (0, 0) encodes the point at infinity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GrumpkinPoint {
+    pub x: Fr,
+    pub y: Fr,
+}
+
+/// Jacobian coordinates (X/Z², Y/Z³); Z = 0 is the point at infinity.
+#[derive(Clone, Copy, Debug)]
+struct Jacobian {
+    x: Fr,
+    y: Fr,
+    z: Fr,
+}
+
+/// b = −17.
+const CURVE_B: Fr =
+    Fr::from_str_const("0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593effffff0");
+
+/// y of bb's Grumpkin generator, √−16.
+const GENERATOR_Y: Fr =
+    Fr::from_str_const("0x0000000000000002cf135e7506a45d632d270d45f1181294833fc48d823f272c");
+
+impl GrumpkinPoint {
+    pub fn infinity() -> Self {
+        GrumpkinPoint {
+            x: Fr::zero(),
+            y: Fr::zero(),
+        }
+    }
+
+    /// bb's Grumpkin generator (1, √−16).
+    pub fn generator() -> Self {
+        GrumpkinPoint {
+            x: Fr::one(),
+            y: GENERATOR_Y,
+        }
+    }
+
+    pub fn is_infinity(&self) -> bool {
+        self.x.is_zero() && self.y.is_zero()
+    }
+
+    pub fn is_on_curve(&self) -> bool {
+        self.is_infinity() || self.y * self.y == self.x * self.x * self.x + CURVE_B
+    }
+
+    pub fn scalar_mul(&self, scalar: &Fq) -> GrumpkinPoint {
+        Jacobian::from(*self).mul(scalar).to_affine()
+    }
+}
+
+impl Add for GrumpkinPoint {
+    type Output = GrumpkinPoint;
+    fn add(self, rhs: GrumpkinPoint) -> GrumpkinPoint {
+        Jacobian::from(self).add(&Jacobian::from(rhs)).to_affine()
+    }
+}
+
+impl Neg for GrumpkinPoint {
+    type Output = GrumpkinPoint;
+    fn neg(self) -> GrumpkinPoint {
+        if self.is_infinity() {
+            return self;
+        }
+        GrumpkinPoint {
+            x: self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl From<GrumpkinPoint> for Jacobian {
+    fn from(p: GrumpkinPoint) -> Self {
+        if p.is_infinity() {
+            Jacobian::infinity()
+        } else {
+            Jacobian {
+                x: p.x,
+                y: p.y,
+                z: Fr::one(),
+            }
+        }
+    }
+}
+
+impl Jacobian {
+    fn infinity() -> Self {
+        Jacobian {
+            x: Fr::one(),
+            y: Fr::one(),
+            z: Fr::zero(),
+        }
+    }
+
+    fn is_infinity(&self) -> bool {
+        self.z.is_zero()
+    }
+
+    fn to_affine(self) -> GrumpkinPoint {
+        match self.z.inverse() {
+            None => GrumpkinPoint::infinity(),
+            Some(z_inv) => {
+                let z_inv2 = z_inv * z_inv;
+                GrumpkinPoint {
+                    x: self.x * z_inv2,
+                    y: self.y * z_inv2 * z_inv,
+                }
+            }
+        }
+    }
+
+    // dbl-2009-l (a = 0)
+    fn double(&self) -> Self {
+        if self.is_infinity() || self.y.is_zero() {
+            return Jacobian::infinity();
+        }
+        let a = self.x * self.x;
+        let b = self.y * self.y;
+        let c = b * b;
+        let xb = self.x + b;
+        let d = xb * xb - a - c;
+        let d = d + d;
+        let e = a + a + a;
+        let f = e * e;
+        let x3 = f - d - d;
+        let c8 = c + c;
+        let c8 = c8 + c8;
+        let c8 = c8 + c8;
+        let y3 = e * (d - x3) - c8;
+        let yz = self.y * self.z;
+        Jacobian {
+            x: x3,
+            y: y3,
+            z: yz + yz,
+        }
+    }
+
+    // add-2007-bl
+    fn add(&self, other: &Self) -> Self {
+        if self.is_infinity() {
+            return *other;
+        }
+        if other.is_infinity() {
+            return *self;
+        }
+        let z1z1 = self.z * self.z;
+        let z2z2 = other.z * other.z;
+        let u1 = self.x * z2z2;
+        let u2 = other.x * z1z1;
+        let s1 = self.y * other.z * z2z2;
+        let s2 = other.y * self.z * z1z1;
+        let h = u2 - u1;
+        let r = s2 - s1;
+        if h.is_zero() {
+            return if r.is_zero() {
+                self.double()
+            } else {
+                Jacobian::infinity()
+            };
+        }
+        let r = r + r;
+        let h2 = h + h;
+        let i = h2 * h2;
+        let j = h * i;
+        let v = u1 * i;
+        let x3 = r * r - j - v - v;
+        let s1j = s1 * j;
+        let y3 = r * (v - x3) - s1j - s1j;
+        let z12 = self.z + other.z;
+        let z3 = (z12 * z12 - z1z1 - z2z2) * h;
+        Jacobian {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+
+    fn mul(&self, scalar: &Fq) -> Self {
+        let limbs = scalar.into_bigint().0;
+        let mut acc = Jacobian::infinity();
+        for bit in (0..256).rev() {
+            acc = acc.double();
+            if (limbs[bit / 64] >> (bit % 64)) & 1 == 1 {
+                acc = acc.add(self);
+            }
+        }
+        acc
+    }
+}
+
+/// Window width for the bucket MSM.
+const MSM_WINDOW_BITS: usize = 8;
+
+/// Multi-scalar multiplication ∑ sᵢ·Pᵢ (bucket method).
+pub fn msm(points: &[GrumpkinPoint], scalars: &[Fq]) -> Result<GrumpkinPoint, &'static str> {
+    if points.len() != scalars.len() {
+        return Err("grumpkin msm len mismatch");
+    }
+    let limbs: Vec<[u64; 4]> = scalars.iter().map(|s| s.into_bigint().0).collect();
+    let windows = (Fq::MODULUS_BIT_SIZE as usize).div_ceil(MSM_WINDOW_BITS);
+    let mask = (1u64 << MSM_WINDOW_BITS) - 1;
+
+    let mut acc = Jacobian::infinity();
+    let mut buckets = vec![Jacobian::infinity(); (1 << MSM_WINDOW_BITS) - 1];
+    for w in (0..windows).rev() {
+        for _ in 0..MSM_WINDOW_BITS {
+            acc = acc.double();
+        }
+        for b in buckets.iter_mut() {
+            *b = Jacobian::infinity();
+        }
+        let shift = w * MSM_WINDOW_BITS;
+        for (p, k) in points.iter().zip(limbs.iter()) {
+            // windows are byte-aligned, so they never straddle two limbs
+            let idx = ((k[shift / 64] >> (shift % 64)) & mask) as usize;
+            if idx != 0 {
+                buckets[idx - 1] = buckets[idx - 1].add(&Jacobian::from(*p));
+            }
+        }
+        let mut running = Jacobian::infinity();
+        let mut sum = Jacobian::infinity();
+        for b in buckets.iter().rev() {
+            running = running.add(b);
+            sum = sum.add(&running);
+        }
+        acc = acc.add(&sum);
+    }
+    Ok(acc.to_affine())
+}
@@ -1,7 +1,62 @@
-use soroban_sdk::Bytes;
+use crate::backend::Backend;
+use soroban_poseidon::{poseidon2_hash, Field};
+use soroban_sdk::{crypto::BnScalar, Bytes, Vec, U256};
 
 /// Compute Keccak-256 using the Soroban host function.
 #[inline(always)]
 pub fn hash32(data: &Bytes) -> [u8; 32] {
     data.env().crypto().keccak256(data).to_array()
 }
+
+/// Poseidon2 sponge (t = 4) over the 32-byte big-endian words of `data`,
+/// matching bb's `--oracle_hash poseidon2` transcript.
+pub fn poseidon2_hash32(data: &Bytes) -> [u8; 32] {
+    let env = data.env();
+    let modulus = <BnScalar as Field>::modulus(env);
+    let mut inputs = Vec::new(env);
+    let mut idx = 0u32;
+    while idx < data.len() {
+        let word = data.slice(idx..idx + 32);
+        inputs.push_back(U256::from_be_bytes(env, &word).rem_euclid(&modulus));
+        idx += 32;
+    }
+    let mut out = [0u8; 32];
+    poseidon2_hash::<4, BnScalar>(env, &inputs)
+        .to_be_bytes()
+        .copy_into_slice(&mut out);
+    out
+}
+
+/// Hash used by the Fiat–Shamir transcript to derive challenges.
+pub trait TranscriptHasher {
+    /// `None` if `backend` cannot compute this hash.
+    fn hash<B: Backend>(&self, backend: &B, data: &B::Bytes) -> Option<[u8; 32]>;
+}
+
+/// Oracle hash the prover was run with (`bb prove --oracle_hash`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OracleHash {
+    #[default]
+    Keccak,
+    Poseidon2,
+}
+
+impl OracleHash {
+    /// Whether `B` can compute this hash.
+    pub fn available<B: Backend>(&self) -> bool {
+        match self {
+            OracleHash::Keccak => true,
+            OracleHash::Poseidon2 => B::HAS_POSEIDON2,
+        }
+    }
+}
+
+impl TranscriptHasher for OracleHash {
+    #[inline(always)]
+    fn hash<B: Backend>(&self, backend: &B, data: &B::Bytes) -> Option<[u8; 32]> {
+        match self {
+            OracleHash::Keccak => Some(backend.keccak256(data)),
+            OracleHash::Poseidon2 => backend.poseidon2(data),
+        }
+    }
+}
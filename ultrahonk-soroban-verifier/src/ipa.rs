@@ -0,0 +1,191 @@
+//! Grumpkin inner-product-argument (IPA) verifier for UltraRollupHonk.
+//!
+//! The opening claim travels in the honk proof's public inputs; the opening
+//! proof is checked against a caller-supplied Grumpkin SRS of
+//! 2^`CONST_ECCVM_LOG_N` points. The SRS MSM alone is ~65k point additions,
+//! which is far beyond a single Soroban transaction: this runs off-chain
+//! (tests, indexers) or wherever that budget is available.
+
+use crate::{
+    backend::{Backend, ByteBuf},
+    ec::{limbs_to_coord, FQ_MODULUS_BE},
+    field::Fr,
+    grumpkin::{msm, GrumpkinPoint},
+    hash::OracleHash,
+    transcript::{hash_to_fr, split_challenge},
+    types::{CONST_ECCVM_LOG_N, IPA_CLAIM_SIZE, IPA_PROOF_LENGTH},
+    utils::combine_limbs,
+};
+use ark_bn254::Fq;
+use ark_ff::{AdditiveGroup, Field, PrimeField};
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+/// Opening claim C = Commit(p), p(x) = v.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IpaClaim {
+    pub challenge: Fq,
+    pub evaluation: Fq,
+    pub commitment: GrumpkinPoint,
+}
+
+impl IpaClaim {
+    /// Decode the public-input encoding: challenge and evaluation as four
+    /// 68-bit limbs each, then the commitment's (x, y).
+    pub fn from_fields(fields: &[Fr; IPA_CLAIM_SIZE]) -> Result<Self, &'static str> {
+        let challenge = Fq::from_be_bytes_mod_order(&limbs_to_coord(&fields[0..4])?);
+        let evaluation = Fq::from_be_bytes_mod_order(&limbs_to_coord(&fields[4..8])?);
+        let commitment = GrumpkinPoint {
+            x: fields[8],
+            y: fields[9],
+        };
+        if !commitment.is_on_curve() {
+            return Err("ipa commitment not on curve");
+        }
+        Ok(IpaClaim {
+            challenge,
+            evaluation,
+            commitment,
+        })
+    }
+}
+
+fn fr_to_fq(fr: &Fr) -> Fq {
+    Fq::from_be_bytes_mod_order(&fr.to_bytes())
+}
+
+fn read_point(fields: &[Fr], cur: &mut usize) -> Result<GrumpkinPoint, &'static str> {
+    let p = GrumpkinPoint {
+        x: fields[*cur],
+        y: fields[*cur + 1],
+    };
+    *cur += 2;
+    if p.is_on_curve() {
+        Ok(p)
+    } else {
+        Err("ipa point not on curve")
+    }
+}
+
+/// Next Poseidon2 challenge over `round`, chained on `previous`; the lower
+/// 128 bits become a Grumpkin scalar.
+fn next_challenge<B: Backend>(
+    backend: &B,
+    previous: Option<Fr>,
+    round: &[Fr],
+) -> Result<(Fq, Fr), &'static str> {
+    let mut data = backend.bytes();
+    if let Some(prev) = previous {
+        data.extend_from_slice(&prev.to_bytes());
+    }
+    for f in round {
+        data.extend_from_slice(&f.to_bytes());
+    }
+    let c = hash_to_fr(backend, &OracleHash::Poseidon2, &data)?;
+    Ok((fr_to_fq(&split_challenge(c).0), c))
+}
+
+/// Verify an IPA opening proof for `claim` against `srs`.
+pub fn verify_ipa<B: Backend>(
+    backend: &B,
+    claim: &IpaClaim,
+    proof: &[Fr; IPA_PROOF_LENGTH],
+    srs: &[GrumpkinPoint],
+) -> Result<(), &'static str> {
+    let log_n = CONST_ECCVM_LOG_N;
+    let n = 1usize << log_n;
+    if proof[0] != Fr::from_u64(n as u64) {
+        return Err("ipa poly length");
+    }
+    if srs.len() < n {
+        return Err("grumpkin srs too short");
+    }
+
+    // 1) generator challenge and C' = C + v·(ξ·G)
+    let (generator_challenge, mut previous) = next_challenge(backend, None, &proof[..1])?;
+    if generator_challenge == Fq::ZERO {
+        return Err("ipa generator challenge is zero");
+    }
+    let aux = GrumpkinPoint::generator().scalar_mul(&generator_challenge);
+    let mut c_zero = claim.commitment + aux.scalar_mul(&claim.evaluation);
+
+    // 2) rounds: C_0 = C' + Σ (u_i⁻¹·L_i + u_i·R_i)
+    let mut cur = 1usize;
+    let mut inv = [Fq::ZERO; CONST_ECCVM_LOG_N];
+    let mut points = Vec::with_capacity(2 * log_n);
+    let mut scalars = Vec::with_capacity(2 * log_n);
+    for u_inv in inv.iter_mut() {
+        let round = &proof[cur..cur + 4];
+        let l = read_point(proof, &mut cur)?;
+        let r = read_point(proof, &mut cur)?;
+        let (u, c) = next_challenge(backend, Some(previous), round)?;
+        previous = c;
+        *u_inv = u.inverse().ok_or("ipa round challenge is zero")?;
+        points.push(l);
+        scalars.push(*u_inv);
+        points.push(r);
+        scalars.push(u);
+    }
+    c_zero = c_zero + msm(&points, &scalars)?;
+
+    // 3) b_0 = Π (1 + u_{k-1-i}⁻¹·x^{2^i})
+    let mut b_zero = Fq::ONE;
+    let mut x_pow = claim.challenge;
+    for i in 0..log_n {
+        b_zero *= Fq::ONE + inv[log_n - 1 - i] * x_pow;
+        x_pow.square_in_place();
+    }
+
+    // 4) G_0 = ⟨s, SRS⟩ with s_i = Π_{bit j of i} u_{k-1-j}⁻¹
+    let mut s = vec![Fq::ONE; n];
+    for j in 0..log_n {
+        let half = 1usize << j;
+        let factor = inv[log_n - 1 - j];
+        for i in 0..half {
+            s[half + i] = s[i] * factor;
+        }
+    }
+    let g_zero = msm(&srs[..n], &s)?;
+    if read_point(proof, &mut cur)? != g_zero {
+        return Err("ipa G_0 mismatch");
+    }
+
+    // 5) C_0 == a_0·G_0 + (a_0·b_0)·(ξ·G)
+    let a_zero = combine_limbs(&proof[cur].to_bytes(), &proof[cur + 1].to_bytes())
+        .map_err(|_| "non-canonical ipa a_0")?;
+    if a_zero >= FQ_MODULUS_BE {
+        return Err("non-canonical ipa a_0");
+    }
+    let a_zero = Fq::from_be_bytes_mod_order(&a_zero);
+    let rhs = g_zero.scalar_mul(&a_zero) + aux.scalar_mul(&(a_zero * b_zero));
+    if c_zero != rhs {
+        return Err("ipa opening check failed");
+    }
+    Ok(())
+}
+
+/// Parse a Grumpkin SRS given as 64-byte big-endian (x, y) pairs.
+pub fn load_grumpkin_srs(bytes: &[u8]) -> Result<Vec<GrumpkinPoint>, &'static str> {
+    if !bytes.len().is_multiple_of(64) {
+        return Err("grumpkin srs must be 64-byte aligned");
+    }
+    bytes
+        .chunks_exact(64)
+        .map(|c| {
+            let mut x = [0u8; 32];
+            let mut y = [0u8; 32];
+            x.copy_from_slice(&c[..32]);
+            y.copy_from_slice(&c[32..]);
+            let p = GrumpkinPoint {
+                x: Fr::from_bytes(&x),
+                y: Fr::from_bytes(&y),
+            };
+            if p.is_on_curve() {
+                Ok(p)
+            } else {
+                Err("grumpkin srs point not on curve")
+            }
+        })
+        .collect()
+}
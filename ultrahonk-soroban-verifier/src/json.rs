@@ -0,0 +1,83 @@
+//! Loaders for bb's JSON field artifacts (`--output_format bytes_and_fields`):
+//! `vk_fields.json`, `proof_fields.json` and `public_inputs_fields.json`,
+//! each a JSON array of hex field strings.
+//!
+//! A file that is not such an array is `ParseError::WrongLength`; an entry
+//! that is not a canonical hex field element is `NonCanonicalScalar`.
+
+use crate::field::Fr;
+use crate::format::{VkFormat, VK_NUM_POINTS};
+use crate::types::{ParseError, Proof, VerificationKey};
+use crate::utils::{combine_limbs, load_proof, load_vk_with_format};
+use soroban_sdk::{Bytes, Env};
+
+/// Header fields of `vk_fields.json`.
+const VK_HEADER_FIELDS: usize = 3;
+
+/// Parse a JSON array of hex strings into 32-byte big-endian words.
+pub fn parse_fields(json: &str) -> Result<Vec<[u8; 32]>, ParseError> {
+    let entries: Vec<String> = serde_json::from_str(json).map_err(|_| ParseError::WrongLength)?;
+    entries
+        .iter()
+        .map(|hex| Ok(Fr::try_from_str(hex)?.to_bytes()))
+        .collect()
+}
+
+/// Load a bb v0.87.0 `vk_fields.json`: circuit_size, public_inputs_size and
+/// pub_inputs_offset, then each commitment as (x_lo, x_hi, y_lo, y_hi)
+/// 136-bit limbs.
+pub fn load_vk_from_json(json: &str) -> Result<VerificationKey, ParseError> {
+    let fields = parse_fields(json)?;
+    if fields.len() != VK_HEADER_FIELDS + 4 * VK_NUM_POINTS {
+        return Err(ParseError::WrongLength);
+    }
+
+    let mut header = [0u64; VK_HEADER_FIELDS];
+    for (h, word) in header.iter_mut().zip(&fields) {
+        *h = u64_field(word)?;
+    }
+    let [circuit_size, public_inputs_size, pub_inputs_offset] = header;
+    if !circuit_size.is_power_of_two() {
+        return Err(ParseError::BadHeader);
+    }
+
+    // Re-encode in the `VkFormat::V0_87` binary layout.
+    let mut bytes = Vec::with_capacity(VkFormat::V0_87.vk_bytes());
+    for h in [
+        circuit_size,
+        circuit_size.trailing_zeros() as u64,
+        public_inputs_size,
+        pub_inputs_offset,
+    ] {
+        bytes.extend_from_slice(&h.to_be_bytes());
+    }
+    for limbs in fields[VK_HEADER_FIELDS..].chunks(2) {
+        bytes.extend_from_slice(&combine_limbs(&limbs[0], &limbs[1])?);
+    }
+    load_vk_with_format(&bytes, VkFormat::V0_87)
+}
+
+/// Load a plain bb v0.87.0 `proof_fields.json`.
+pub fn load_proof_from_json(json: &str) -> Result<Proof, ParseError> {
+    load_proof(&parse_fields(json)?.concat())
+}
+
+/// Proof bytes from `proof_fields.json`, as the verifier takes them.
+pub fn proof_bytes_from_json(env: &Env, json: &str) -> Result<Bytes, ParseError> {
+    Ok(Bytes::from_slice(env, &parse_fields(json)?.concat()))
+}
+
+/// Public-input bytes from `public_inputs_fields.json`, as the verifier
+/// takes them.
+pub fn public_inputs_from_json(env: &Env, json: &str) -> Result<Bytes, ParseError> {
+    Ok(Bytes::from_slice(env, &parse_fields(json)?.concat()))
+}
+
+fn u64_field(word: &[u8; 32]) -> Result<u64, ParseError> {
+    if word[..24].iter().any(|&b| b != 0) {
+        return Err(ParseError::BadHeader);
+    }
+    let mut tail = [0u8; 8];
+    tail.copy_from_slice(&word[24..]);
+    Ok(u64::from_be_bytes(tail))
+}
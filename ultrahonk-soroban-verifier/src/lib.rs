@@ -3,10 +3,21 @@
 #[cfg(not(feature = "std"))]
 extern crate alloc;
 
+#[cfg(feature = "std")]
+pub mod abi;
+pub mod backend;
+pub mod compact;
 pub mod debug;
 pub mod ec;
 pub mod field;
+pub mod flavor;
+pub mod format;
+pub mod grumpkin;
 pub mod hash;
+pub mod ipa;
+#[cfg(feature = "std")]
+pub mod json;
+pub mod public_inputs;
 pub mod relations;
 pub mod shplemini;
 pub mod sumcheck;
@@ -14,7 +25,15 @@ pub mod transcript;
 pub mod types;
 pub mod utils;
 pub mod verifier;
+pub mod view;
 pub const PROOF_FIELDS: usize = 456;
 pub const PROOF_BYTES: usize = PROOF_FIELDS * 32;
+pub const ZK_PROOF_FIELDS: usize = 507;
+pub const ZK_PROOF_BYTES: usize = ZK_PROOF_FIELDS * 32;
+pub const ROLLUP_PROOF_FIELDS: usize =
+    PROOF_FIELDS + types::IPA_CLAIM_SIZE + types::IPA_PROOF_LENGTH;
+pub const ROLLUP_PROOF_BYTES: usize = ROLLUP_PROOF_FIELDS * 32;
 
+pub use hash::OracleHash;
+pub use types::{ParseError, VkError};
 pub use verifier::UltraHonkVerifier;
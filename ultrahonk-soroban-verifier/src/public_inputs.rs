@@ -0,0 +1,75 @@
+//! Typed access to the `public_inputs` bytes a contract receives: one
+//! 32-byte big-endian word per flattened public input, in the order of the
+//! Noir ABI (see [`abi::PublicAbi::index_of`](crate::abi) under `std`).
+//!
+//! A Noir value that spans several words (array, struct, string) starts at
+//! its ABI index and is read word by word.
+
+use crate::backend::ByteBuf;
+use crate::field::Fr;
+use crate::types::ParseError;
+
+/// Public inputs read in place. Every accessor checks the word it returns;
+/// an index past the end is `ParseError::WrongLength`.
+#[derive(Clone, Debug)]
+pub struct PublicInputs<'a, D: ByteBuf> {
+    bytes: &'a D,
+}
+
+impl<'a, D: ByteBuf> PublicInputs<'a, D> {
+    /// Rejects bytes that are not a whole number of words.
+    pub fn new(bytes: &'a D) -> Result<Self, ParseError> {
+        if !bytes.len().is_multiple_of(32) {
+            return Err(ParseError::WrongLength);
+        }
+        Ok(Self { bytes })
+    }
+
+    /// Number of 32-byte words.
+    pub fn len(&self) -> usize {
+        self.bytes.len() as usize / 32
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Word `index` as a canonical field element's big-endian bytes.
+    pub fn field(&self, index: usize) -> Result<[u8; 32], ParseError> {
+        Ok(self.fr(index)?.to_bytes())
+    }
+
+    pub fn fr(&self, index: usize) -> Result<Fr, ParseError> {
+        Fr::from_bytes_canonical(&self.word(index)?).ok_or(ParseError::NonCanonicalScalar)
+    }
+
+    /// Word `index` as a Noir `u64` (or narrower unsigned integer); larger
+    /// values are `NonCanonicalScalar`.
+    pub fn u64(&self, index: usize) -> Result<u64, ParseError> {
+        let word = self.word(index)?;
+        if word[..24].iter().any(|&b| b != 0) {
+            return Err(ParseError::NonCanonicalScalar);
+        }
+        let mut tail = [0u8; 8];
+        tail.copy_from_slice(&word[24..]);
+        Ok(u64::from_be_bytes(tail))
+    }
+
+    /// Word `index` as a Noir `bool`: 0 or 1.
+    pub fn bool(&self, index: usize) -> Result<bool, ParseError> {
+        match self.u64(index)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(ParseError::NonCanonicalScalar),
+        }
+    }
+
+    fn word(&self, index: usize) -> Result<[u8; 32], ParseError> {
+        if index >= self.len() {
+            return Err(ParseError::WrongLength);
+        }
+        let mut out = [0u8; 32];
+        self.bytes.read_into((index * 32) as u32, &mut out);
+        Ok(out)
+    }
+}
@@ -4,6 +4,7 @@
 //! scalar which is then batched with the alpha challenges.
 
 use crate::field::Fr;
+use crate::flavor::Flavor;
 use crate::types::{RelationParameters, Wire, NUMBER_OF_SUBRELATIONS};
 
 #[cfg(feature = "std")]
@@ -12,19 +13,33 @@ macro_rules! println {
 }
 
 /// Precomputed NEG_HALF = (p - 1)/2 in BN254 scalar field.
-fn neg_half() -> Fr {
-    Fr::from_str("0x183227397098d014dc2822db40c0ac2e9419f4243cdcb848a1f0fac9f8000000")
-}
+pub const NEG_HALF: Fr =
+    Fr::from_str_const("0x183227397098d014dc2822db40c0ac2e9419f4243cdcb848a1f0fac9f8000000");
 
 /// Internal matrix diagonal values for Poseidon hash
-fn internal_matrix_diagonal() -> [Fr; 4] {
-    [
-        Fr::from_str("0x10dc6e9c006ea38b04b1e03b4bd9490c0d03f98929ca1d7fb56821fd19d3b6e7"),
-        Fr::from_str("0x0c28145b6a44df3e0149b3d0a30b3bb599df9756d4dd9b84a86b38cfb45a740b"),
-        Fr::from_str("0x00544b8338791518b2c7645a50392798b21f75bb60e3596170067d00141cac15"),
-        Fr::from_str("0x222c01175718386f2e2e82eb122789e352e105a3b8fa852613bc534433ee428b"),
-    ]
-}
+pub const INTERNAL_MATRIX_DIAGONAL: [Fr; 4] = [
+    Fr::from_str_const("0x10dc6e9c006ea38b04b1e03b4bd9490c0d03f98929ca1d7fb56821fd19d3b6e7"),
+    Fr::from_str_const("0x0c28145b6a44df3e0149b3d0a30b3bb599df9756d4dd9b84a86b38cfb45a740b"),
+    Fr::from_str_const("0x00544b8338791518b2c7645a50392798b21f75bb60e3596170067d00141cac15"),
+    Fr::from_str_const("0x222c01175718386f2e2e82eb122789e352e105a3b8fa852613bc534433ee428b"),
+];
+
+/// 2^68, the auxiliary relation's non-native field limb size.
+pub const LIMB_SIZE: Fr = Fr::from_str_const("0x100000000000000000");
+
+/// 2^14, the shift between the sublimbs of a limb.
+pub const SUBLIMB_SHIFT: Fr = Fr::from_u64(1 << 14);
+
+const ONE: Fr = Fr::one();
+const TWO: Fr = Fr::from_u64(2);
+const THREE: Fr = Fr::from_u64(3);
+const NINE: Fr = Fr::from_u64(9);
+const MINUS_ONE: Fr =
+    Fr::from_str_const("0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000");
+const MINUS_TWO: Fr =
+    Fr::from_str_const("0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593efffffff");
+const MINUS_THREE: Fr =
+    Fr::from_str_const("0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593effffffe");
 
 /// Helper to index into the wire array.
 fn wire(vals: &[Fr], w: Wire) -> Fr {
@@ -32,16 +47,20 @@ fn wire(vals: &[Fr], w: Wire) -> Fr {
 }
 
 /// Accumulate the two arithmetic subrelations (indices 0 and 1).
-fn accumulate_arithmetic_relation(p: &[Fr], evals: &mut [Fr], domain_sep: Fr) {
+fn accumulate_arithmetic_relation(
+    p: &[Fr],
+    _rp: &RelationParameters,
+    evals: &mut [Fr],
+    domain_sep: Fr,
+) {
     // Relation 0
     {
         let q_arith = wire(p, Wire::QArith);
-        let neg_half = neg_half();
-        let mut accum = (q_arith - Fr::from_u64(3))
+        let mut accum = (q_arith - THREE)
             * wire(p, Wire::Qm)
             * wire(p, Wire::Wr)
             * wire(p, Wire::Wl)
-            * neg_half;
+            * NEG_HALF;
         accum = accum
             + wire(p, Wire::Ql) * wire(p, Wire::Wl)
             + wire(p, Wire::Qr) * wire(p, Wire::Wr)
@@ -56,11 +75,7 @@ fn accumulate_arithmetic_relation(p: &[Fr], evals: &mut [Fr], domain_sep: Fr) {
         let q_arith = wire(p, Wire::QArith);
         let mut accum =
             wire(p, Wire::Wl) + wire(p, Wire::W4) - wire(p, Wire::WlShift) + wire(p, Wire::Qm);
-        accum = accum
-            * (q_arith - Fr::from_u64(2))
-            * (q_arith - Fr::from_u64(1))
-            * q_arith
-            * domain_sep;
+        accum = accum * (q_arith - TWO) * (q_arith - ONE) * q_arith * domain_sep;
         evals[1] = accum;
     }
 }
@@ -138,22 +153,22 @@ fn accumulate_log_derivative_lookup_relation(
 }
 
 /// Accumulate the four range-check subrelations (indices 6..9).
-fn accumulate_delta_range_relation(p: &[Fr], evals: &mut [Fr], domain_sep: Fr) {
-    let minus_one = Fr::zero() - Fr::from_u64(1);
-    let minus_two = Fr::zero() - Fr::from_u64(2);
-    let minus_three = Fr::zero() - Fr::from_u64(3);
-
+fn accumulate_delta_range_relation(
+    p: &[Fr],
+    _rp: &RelationParameters,
+    evals: &mut [Fr],
+    domain_sep: Fr,
+) {
     let delta_1 = wire(p, Wire::Wr) - wire(p, Wire::Wl);
     let delta_2 = wire(p, Wire::Wo) - wire(p, Wire::Wr);
     let delta_3 = wire(p, Wire::W4) - wire(p, Wire::Wo);
     let delta_4 = wire(p, Wire::WlShift) - wire(p, Wire::W4);
     let deltas = [delta_1, delta_2, delta_3, delta_4];
-    let negs = [minus_one, minus_two, minus_three];
 
     // Contributions 6..9
     for i in 0..4 {
         let mut acc = deltas[i];
-        for &n in &negs {
+        for &n in &[MINUS_ONE, MINUS_TWO, MINUS_THREE] {
             acc = acc * (deltas[i] + n);
         }
         evals[6 + i] = acc * wire(p, Wire::QRange) * domain_sep;
@@ -161,7 +176,12 @@ fn accumulate_delta_range_relation(p: &[Fr], evals: &mut [Fr], domain_sep: Fr) {
 }
 
 /// Accumulate elliptic-curve subrelations (indices 10..11).
-fn accumulate_elliptic_relation(p: &[Fr], evals: &mut [Fr], domain_sep: Fr) {
+fn accumulate_elliptic_relation(
+    p: &[Fr],
+    _rp: &RelationParameters,
+    evals: &mut [Fr],
+    domain_sep: Fr,
+) {
     let x1 = wire(p, Wire::Wr);
     let y1 = wire(p, Wire::Wo);
     let x2 = wire(p, Wire::WlShift);
@@ -186,13 +206,12 @@ fn accumulate_elliptic_relation(p: &[Fr], evals: &mut [Fr], domain_sep: Fr) {
         (y1 + y3) * delta_x + (x3 - x1) * y_diff
     };
 
-    const B_NEG: u64 = 17;
-    let b_neg = Fr::from_u64(B_NEG);
+    const B_NEG: Fr = Fr::from_u64(17);
 
     let x_double_id = {
-        let x_pow_4 = (y1_sq + b_neg) * x1;
+        let x_pow_4 = (y1_sq + B_NEG) * x1;
         let y1_sqr_mul_4 = y1_sq + y1_sq + y1_sq + y1_sq;
-        let x_pow_4_mul_9 = x_pow_4 * Fr::from_u64(9);
+        let x_pow_4_mul_9 = x_pow_4 * NINE;
         (x3 + x1 + x1) * y1_sqr_mul_4 - x_pow_4_mul_9
     };
     let y_double_id = {
@@ -216,13 +235,6 @@ fn accumulate_auxillary_relation(
     evals: &mut [Fr],
     domain_sep: Fr,
 ) {
-    fn limb_size() -> Fr {
-        Fr::from_str("0x100000000000000000")
-    }
-    fn sublimb_shift() -> Fr {
-        Fr::from_u64(1 << 14)
-    }
-
     let mut limb_subproduct =
         wire(p, Wire::Wl) * wire(p, Wire::WrShift) + wire(p, Wire::WlShift) * wire(p, Wire::Wr);
 
@@ -230,11 +242,10 @@ fn accumulate_auxillary_relation(
         + wire(p, Wire::Wr) * wire(p, Wire::Wo)
         - wire(p, Wire::WoShift);
     non_native_field_gate_2 =
-        non_native_field_gate_2 * limb_size() - wire(p, Wire::W4Shift) + limb_subproduct;
+        non_native_field_gate_2 * LIMB_SIZE - wire(p, Wire::W4Shift) + limb_subproduct;
     non_native_field_gate_2 = non_native_field_gate_2 * wire(p, Wire::Q4);
 
-    limb_subproduct =
-        limb_subproduct * limb_size() + wire(p, Wire::WlShift) * wire(p, Wire::WrShift);
+    limb_subproduct = limb_subproduct * LIMB_SIZE + wire(p, Wire::WlShift) * wire(p, Wire::WrShift);
 
     let non_native_field_gate_1 =
         (limb_subproduct - (wire(p, Wire::Wo) + wire(p, Wire::W4))) * wire(p, Wire::Qo);
@@ -247,16 +258,16 @@ fn accumulate_auxillary_relation(
         (non_native_field_gate_1 + non_native_field_gate_2 + non_native_field_gate_3)
             * wire(p, Wire::Qr);
 
-    let mut limb_accumulator_1 = wire(p, Wire::WrShift) * sublimb_shift() + wire(p, Wire::WlShift);
-    limb_accumulator_1 = limb_accumulator_1 * sublimb_shift() + wire(p, Wire::Wo);
-    limb_accumulator_1 = limb_accumulator_1 * sublimb_shift() + wire(p, Wire::Wr);
-    limb_accumulator_1 = limb_accumulator_1 * sublimb_shift() + wire(p, Wire::Wl);
+    let mut limb_accumulator_1 = wire(p, Wire::WrShift) * SUBLIMB_SHIFT + wire(p, Wire::WlShift);
+    limb_accumulator_1 = limb_accumulator_1 * SUBLIMB_SHIFT + wire(p, Wire::Wo);
+    limb_accumulator_1 = limb_accumulator_1 * SUBLIMB_SHIFT + wire(p, Wire::Wr);
+    limb_accumulator_1 = limb_accumulator_1 * SUBLIMB_SHIFT + wire(p, Wire::Wl);
     limb_accumulator_1 = (limb_accumulator_1 - wire(p, Wire::W4)) * wire(p, Wire::Q4);
 
-    let mut limb_accumulator_2 = wire(p, Wire::WoShift) * sublimb_shift() + wire(p, Wire::WrShift);
-    limb_accumulator_2 = limb_accumulator_2 * sublimb_shift() + wire(p, Wire::WlShift);
-    limb_accumulator_2 = limb_accumulator_2 * sublimb_shift() + wire(p, Wire::W4);
-    limb_accumulator_2 = limb_accumulator_2 * sublimb_shift() + wire(p, Wire::Wo);
+    let mut limb_accumulator_2 = wire(p, Wire::WoShift) * SUBLIMB_SHIFT + wire(p, Wire::WrShift);
+    limb_accumulator_2 = limb_accumulator_2 * SUBLIMB_SHIFT + wire(p, Wire::WlShift);
+    limb_accumulator_2 = limb_accumulator_2 * SUBLIMB_SHIFT + wire(p, Wire::W4);
+    limb_accumulator_2 = limb_accumulator_2 * SUBLIMB_SHIFT + wire(p, Wire::Wo);
     limb_accumulator_2 = (limb_accumulator_2 - wire(p, Wire::W4Shift)) * wire(p, Wire::Qm);
 
     let limb_accumulator_identity = (limb_accumulator_1 + limb_accumulator_2) * wire(p, Wire::Qo);
@@ -331,7 +342,12 @@ fn accumulate_auxillary_relation(
 }
 
 /// Accumulate Poseidon external subrelations (indices 18..21).
-fn accumulate_poseidon_external_relation(p: &[Fr], evals: &mut [Fr], domain_sep: Fr) {
+fn accumulate_poseidon_external_relation(
+    p: &[Fr],
+    _rp: &RelationParameters,
+    evals: &mut [Fr],
+    domain_sep: Fr,
+) {
     let s1 = wire(p, Wire::Wl) + wire(p, Wire::Ql);
     let s2 = wire(p, Wire::Wr) + wire(p, Wire::Qr);
     let s3 = wire(p, Wire::Wo) + wire(p, Wire::Qo);
@@ -360,14 +376,19 @@ fn accumulate_poseidon_external_relation(p: &[Fr], evals: &mut [Fr], domain_sep:
 }
 
 /// Accumulate Poseidon internal subrelations (indices 22..25).
-fn accumulate_poseidon_internal_relation(p: &[Fr], evals: &mut [Fr], domain_sep: Fr) {
+fn accumulate_poseidon_internal_relation(
+    p: &[Fr],
+    _rp: &RelationParameters,
+    evals: &mut [Fr],
+    domain_sep: Fr,
+) {
     let u1_int = (wire(p, Wire::Wl) + wire(p, Wire::Ql)).pow(5);
     let u2_int = wire(p, Wire::Wr);
     let u3_int = wire(p, Wire::Wo);
     let u4_int = wire(p, Wire::W4);
     let q_poseidon = wire(p, Wire::QPoseidon2Internal);
     let u_sum = u1_int + u2_int + u3_int + u4_int;
-    let diag = internal_matrix_diagonal();
+    let diag = INTERNAL_MATRIX_DIAGONAL;
 
     let w1 = u1_int * diag[0] + u_sum;
     let w2 = u2_int * diag[1] + u_sum;
@@ -380,56 +401,49 @@ fn accumulate_poseidon_internal_relation(p: &[Fr], evals: &mut [Fr], domain_sep:
     evals[25] = (w4 - wire(p, Wire::W4Shift)) * q_poseidon * domain_sep;
 }
 
-/// Batch all NUM_SUBRELATIONS = 26 subrelations with the alpha challenges.
-fn scale_and_batch_subrelations(evaluations: &[Fr], subrelation_challenges: &[Fr]) -> Fr {
+/// Batch the first `n` subrelations with the alpha challenges.
+fn scale_and_batch_subrelations(evaluations: &[Fr], subrelation_challenges: &[Fr], n: usize) -> Fr {
     let mut accumulator = evaluations[0];
-    for i in 1..NUMBER_OF_SUBRELATIONS {
+    for i in 1..n {
         accumulator = accumulator + evaluations[i] * subrelation_challenges[i - 1];
     }
     accumulator
 }
 
-/// Main entrypoint: accumulate all subrelations and batch with alphas.
-pub fn accumulate_relation_evaluations(
+/// One relation: writes its subrelations, scaled by the domain separator,
+/// at their fixed indices in the evaluation array.
+pub type Relation = fn(&[Fr], &RelationParameters, &mut [Fr], Fr);
+
+/// The UltraHonk relation set, covering subrelations 0..NUMBER_OF_SUBRELATIONS.
+pub const ULTRA_RELATIONS: [Relation; 8] = [
+    accumulate_arithmetic_relation,
+    accumulate_permutation_relation,
+    accumulate_log_derivative_lookup_relation,
+    accumulate_delta_range_relation,
+    accumulate_elliptic_relation,
+    accumulate_auxillary_relation,
+    accumulate_poseidon_external_relation,
+    accumulate_poseidon_internal_relation,
+];
+
+/// Main entrypoint: accumulate the flavor's subrelations and batch with alphas.
+pub fn accumulate_relation_evaluations<F: Flavor>(
     purported_evaluations: &[Fr],
     rp: &RelationParameters,
     alphas: &[Fr],
     pow_partial_eval: Fr,
 ) -> Fr {
+    debug_assert!(F::NUMBER_OF_SUBRELATIONS <= NUMBER_OF_SUBRELATIONS);
     let mut evaluations = [Fr::zero(); NUMBER_OF_SUBRELATIONS];
 
-    accumulate_arithmetic_relation(purported_evaluations, &mut evaluations, pow_partial_eval);
-    accumulate_permutation_relation(
-        purported_evaluations,
-        rp,
-        &mut evaluations,
-        pow_partial_eval,
-    );
-    accumulate_log_derivative_lookup_relation(
-        purported_evaluations,
-        rp,
-        &mut evaluations,
-        pow_partial_eval,
-    );
-    accumulate_delta_range_relation(purported_evaluations, &mut evaluations, pow_partial_eval);
-    accumulate_elliptic_relation(purported_evaluations, &mut evaluations, pow_partial_eval);
-    accumulate_auxillary_relation(
-        purported_evaluations,
-        rp,
-        &mut evaluations,
-        pow_partial_eval,
-    );
-    accumulate_poseidon_external_relation(
-        purported_evaluations,
-        &mut evaluations,
-        pow_partial_eval,
-    );
-    accumulate_poseidon_internal_relation(
-        purported_evaluations,
-        &mut evaluations,
-        pow_partial_eval,
-    );
-
-    let accumulator = scale_and_batch_subrelations(&evaluations, alphas);
-    accumulator
+    for relation in F::RELATIONS {
+        relation(
+            purported_evaluations,
+            rp,
+            &mut evaluations,
+            pow_partial_eval,
+        );
+    }
+
+    scale_and_batch_subrelations(&evaluations, alphas, F::NUMBER_OF_SUBRELATIONS)
 }
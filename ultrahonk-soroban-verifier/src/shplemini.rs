@@ -1,21 +1,89 @@
 //! Shplemini batch-opening verifier for BN254
+use crate::backend::{Backend, ByteBuf};
 use crate::ec::helpers::negate;
-use crate::ec::{g1_msm, pairing_check};
+use crate::ec::{aggregate_pairing_points, g1_msm, merge_msm_terms, pairing_check, MsmBackend};
 use crate::field::{batch_inverse, Fr};
+use crate::flavor::Flavor;
 use crate::trace;
 use crate::types::{
-    G1Point, Proof, Transcript, VerificationKey, CONST_PROOF_SIZE_LOG_N, NUMBER_OF_ENTITIES,
-    NUMBER_TO_BE_SHIFTED, NUMBER_UNSHIFTED,
+    G1Point, Transcript, VerificationKey, CONST_PROOF_SIZE_LOG_N, LIBRA_COMMITMENTS,
+    LIBRA_EVALUATIONS, LIBRA_UNIVARIATES_LENGTH, NUMBER_OF_ENTITIES, SUBGROUP_SIZE,
 };
-use soroban_sdk::Env;
+use crate::view::ProofView;
+
+/// Generator of the order-SUBGROUP_SIZE multiplicative subgroup used by the
+/// small-subgroup IPA, 5^((p - 1) / 256).
+const SUBGROUP_GENERATOR: Fr =
+    Fr::from_str_const("0x07b0c561a6148404f086204a9f36ffb0617942546750f230c893619174a57a76");
+
+const SUBGROUP_GENERATOR_INVERSE: Fr =
+    Fr::from_str_const("0x204bd3277422fad364751ad938e2b5e6a54cf8c68712848a692c553d0329f5d6");
+
+/// Write the VK and proof entity commitments into `coms[start..start + F::NUMBER_OF_ENTITIES]`
+/// in the flavor's order: VK points, unshifted witness points, shifted witness points.
+/// Each witness point is decoded once; the shifted entries copy their unshifted slot.
+fn load_entity_commitments<F: Flavor, D: ByteBuf>(
+    coms: &mut [G1Point],
+    start: usize,
+    vk: &VerificationKey,
+    proof: &ProofView<D>,
+) -> Result<(), &'static str> {
+    let mut j = start;
+    for &w in F::PRECOMPUTED {
+        coms[j] = *vk
+            .commitment(w)
+            .ok_or("shplemini: flavor lists a witness wire as precomputed")?;
+        j += 1;
+    }
+
+    // Unshifted witness commitments
+    let unshifted = j;
+    for &w in F::WITNESS {
+        coms[j] = proof.witness(w);
+        j += 1;
+    }
+    // Shifted witness commitments
+    for w in F::SHIFTED {
+        let i = F::WITNESS
+            .iter()
+            .position(|u| u == w)
+            .ok_or("shplemini: shifted commitment is not a witness commitment")?;
+        coms[j] = coms[unshifted + i];
+        j += 1;
+    }
+    debug_assert_eq!(j, start + F::NUMBER_OF_ENTITIES);
+    Ok(())
+}
+
+fn settle<B: Backend>(backend: &B, p0: &B::G1, p1: &B::G1) -> Result<(), &'static str> {
+    if pairing_check(backend, p0, p1) {
+        Ok(())
+    } else {
+        Err("Shplonk pairing check failed")
+    }
+}
 
 /// Shplemini verification
-pub fn verify_shplemini(
-    env: &Env,
-    proof: &Proof,
+pub fn verify_shplemini<F: Flavor, B: Backend, D: ByteBuf>(
+    backend: &B,
+    proof: &ProofView<D>,
     vk: &VerificationKey,
     tp: &Transcript,
+    msm: MsmBackend,
 ) -> Result<(), &'static str> {
+    let (p0, p1) = shplemini_pairing_points::<F, B, D>(backend, proof, vk, tp, msm)?;
+    settle(backend, &p0, &p1)
+}
+
+/// Shplemini up to the final pairing: the (P0, P1) inputs of
+/// e(P0, [1]₂)·e(P1, [x]₂) = 1, with the recursion accumulator folded in.
+pub fn shplemini_pairing_points<F: Flavor, B: Backend, D: ByteBuf>(
+    backend: &B,
+    proof: &ProofView<D>,
+    vk: &VerificationKey,
+    tp: &Transcript,
+    msm: MsmBackend,
+) -> Result<(B::G1, B::G1), &'static str> {
     // 1) r^{2^i}
     let log_n = vk.log_circuit_size as usize;
     let mut r_pows = [Fr::zero(); CONST_PROOF_SIZE_LOG_N];
@@ -71,16 +139,17 @@ pub fn verify_shplemini(
     let neg0 = inverted[1];
     let gemini_r_inv = inverted[2];
 
-    // 2) allocate arrays
-    // Match Solidity sizing: NUMBER_OF_ENTITIES + CONST_PROOF_SIZE_LOG_N + 2
-    // Layout:
+    // 2) allocate arrays, sized for the largest flavor and round count
+    // Layout, with E = F::NUMBER_OF_ENTITIES and R = proof.rounds():
     //   [0]                 = shplonk_Q
-    //   [1..=40]            = VK + proof entities (NUMBER_OF_ENTITIES)
-    //   [41..=67]           = gemini_fold_comms (CONST_PROOF_SIZE_LOG_N - 1 = 27)
-    //   [68]                = generator (1,2) with const_acc scalar
-    //   [69]                = kzg_quotient with scalar z
+    //   [1..=E]             = VK + proof entities
+    //   [E+1..E+R)          = gemini_fold_comms (R - 1, only the first
+    //                         log_n - 1 are read)
+    //   [E+R]               = generator (1,2) with const_acc scalar
+    //   [E+R+1]             = kzg_quotient with scalar z
     const TOTAL: usize = 1 + NUMBER_OF_ENTITIES + CONST_PROOF_SIZE_LOG_N + 1;
-    trace!("total = {}", TOTAL);
+    let rounds = proof.rounds();
+    trace!("total = {}", 1 + F::NUMBER_OF_ENTITIES + rounds + 1);
     let mut scalars = [Fr::zero(); TOTAL];
     let mut coms = [G1Point::infinity(); TOTAL];
 
@@ -89,20 +158,16 @@ pub fn verify_shplemini(
     let shifted = gemini_r_inv * (pos0 - tp.shplonk_nu * neg0);
     // 4) shplonk_Q
     scalars[0] = Fr::one();
-    coms[0] = proof.shplonk_q.clone();
+    coms[0] = proof.shplonk_q();
 
     // 5) weight sumcheck evals
     let mut rho_pow = Fr::one();
     let mut eval_acc = Fr::zero();
-    let shifted_end = NUMBER_UNSHIFTED + NUMBER_TO_BE_SHIFTED;
-    debug_assert_eq!(NUMBER_OF_ENTITIES, shifted_end);
-    for (idx, eval) in proof
-        .sumcheck_evaluations
-        .iter()
-        .take(NUMBER_OF_ENTITIES)
-        .enumerate()
-    {
-        let scalar = if idx < NUMBER_UNSHIFTED {
+    let shifted_end = F::NUMBER_UNSHIFTED + F::NUMBER_TO_BE_SHIFTED;
+    debug_assert_eq!(F::NUMBER_OF_ENTITIES, shifted_end);
+    let evaluations = proof.sumcheck_evaluations();
+    for (idx, eval) in evaluations[..F::NUMBER_OF_ENTITIES].iter().enumerate() {
+        let scalar = if idx < F::NUMBER_UNSHIFTED {
             -unshifted
         } else {
             -shifted
@@ -112,92 +177,26 @@ pub fn verify_shplemini(
         rho_pow = rho_pow * tp.rho;
     }
     // 6) load VK & proof
-    {
-        let mut j = 1;
-        macro_rules! push {
-            ($f:ident) => {{
-                coms[j] = vk.$f.clone();
-                j += 1;
-            }};
-        }
-        push!(qm);
-        push!(qc);
-        push!(ql);
-        push!(qr);
-        push!(qo);
-        push!(q4);
-        // Match Solidity VK commitment order strictly
-        // 7..13: qLookup, qArith, qDeltaRange, qElliptic, qAux, qPoseidon2External, qPoseidon2Internal
-        push!(q_lookup);
-        push!(q_arith);
-        push!(q_delta_range);
-        push!(q_elliptic);
-        push!(q_aux);
-        push!(q_poseidon2_external);
-        push!(q_poseidon2_internal);
-        push!(s1);
-        push!(s2);
-        push!(s3);
-        push!(s4);
-        push!(id1);
-        push!(id2);
-        push!(id3);
-        push!(id4);
-        push!(t1);
-        push!(t2);
-        push!(t3);
-        push!(t4);
-        push!(lagrange_first);
-        push!(lagrange_last);
-
-        coms[j] = proof.w1.clone();
-        j += 1;
-        coms[j] = proof.w2.clone();
-        j += 1;
-        coms[j] = proof.w3.clone();
-        j += 1;
-        coms[j] = proof.w4.clone();
-        j += 1;
-        coms[j] = proof.z_perm.clone();
-        j += 1;
-        coms[j] = proof.lookup_inverses.clone();
-        j += 1;
-        coms[j] = proof.lookup_read_counts.clone();
-        j += 1;
-        coms[j] = proof.lookup_read_tags.clone();
-        j += 1;
-
-        coms[j] = proof.w1.clone();
-        j += 1;
-        coms[j] = proof.w2.clone();
-        j += 1;
-        coms[j] = proof.w3.clone();
-        j += 1;
-        coms[j] = proof.w4.clone();
-        j += 1;
-        coms[j] = proof.z_perm.clone();
-        j += 1;
-        let _ = j; // silence "assigned but never read" in non-trace builds
-    }
+    load_entity_commitments::<F, D>(&mut coms, 1, vk, proof)?;
 
     // 7) folding rounds — use batch-inverted denominators
+    let a_evaluations = proof.gemini_a_evaluations();
     let mut fold_pos = [Fr::zero(); CONST_PROOF_SIZE_LOG_N];
     let mut cur = eval_acc;
     for j in (1..=log_n).rev() {
         let r2 = r_pows[j - 1];
         let u = tp.sumcheck_u_challenges[j - 1];
-        let num = r2 * cur * Fr::from_u64(2)
-            - proof.gemini_a_evaluations[j - 1] * (r2 * (Fr::one() - u) - u);
+        let num = r2 * cur * Fr::from_u64(2) - a_evaluations[j - 1] * (r2 * (Fr::one() - u) - u);
         let den_inv = inverted[3 + (log_n - j)];
         cur = num * den_inv;
         fold_pos[j - 1] = cur;
     }
     // 8) accumulate constant term
-    let mut const_acc = fold_pos[0] * pos0 + proof.gemini_a_evaluations[0] * tp.shplonk_nu * neg0;
+    let mut const_acc = fold_pos[0] * pos0 + a_evaluations[0] * tp.shplonk_nu * neg0;
     let mut v_pow = tp.shplonk_nu * tp.shplonk_nu;
     // 9) further folding + commit — use batch-inverted denominators
     // Base index where fold commitments start
-    let base = 1 + NUMBER_OF_ENTITIES;
+    let base = 1 + F::NUMBER_OF_ENTITIES;
     for j in 1..log_n {
         let pos_inv = inverted[further_base + 2 * (j - 1)];
         let neg_inv = inverted[further_base + 2 * (j - 1) + 1];
@@ -205,21 +204,16 @@ pub fn verify_shplemini(
         let sn = v_pow * tp.shplonk_nu * neg_inv;
 
         scalars[base + j - 1] = -(sp + sn);
-        const_acc = const_acc + proof.gemini_a_evaluations[j] * sn + fold_pos[j] * sp;
+        const_acc = const_acc + a_evaluations[j] * sn + fold_pos[j] * sp;
 
         v_pow = v_pow * tp.shplonk_nu * tp.shplonk_nu;
 
-        coms[base + j - 1] = proof.gemini_fold_comms[j - 1].clone();
-    }
-
-    // Fill remaining (dummy) fold commitments so MSM layout matches Solidity (total 27 entries)
-    for i in (log_n - 1)..(CONST_PROOF_SIZE_LOG_N - 1) {
-        coms[base + i] = proof.gemini_fold_comms[i].clone();
+        coms[base + j - 1] = proof.gemini_fold_comm(j - 1);
     }
 
     // 10) add generator
-    // Generator goes right after all fold commitments (27 entries)
-    let one_idx = base + (CONST_PROOF_SIZE_LOG_N - 1);
+    // Generator goes right after all rounds - 1 fold commitments
+    let one_idx = base + (rounds - 1);
     trace!("one_idx = {}", one_idx);
     coms[one_idx] = G1Point::generator();
     scalars[one_idx] = const_acc;
@@ -227,15 +221,259 @@ pub fn verify_shplemini(
     // 11) add quotient
     let q_idx = one_idx + 1;
     trace!("q_idx = {}", q_idx);
-    coms[q_idx] = proof.kzg_quotient.clone();
+    let kzg_quotient = proof.kzg_quotient();
+    coms[q_idx] = kzg_quotient;
     scalars[q_idx] = tp.shplonk_z;
 
-    // 12) MSM + pairing
-    let p0 = g1_msm(env, &coms, &scalars)?;
-    let p1 = negate(env, &proof.kzg_quotient);
-    if pairing_check(env, &p0, &p1) {
+    // 12) MSM over the compacted terms: shifted w1..w4/z_perm merge into
+    // their unshifted entries, dummy fold commitments drop out
+    let n = merge_msm_terms(&mut coms, &mut scalars)?;
+    let p0 = g1_msm(backend, msm, &coms[..n], &scalars[..n])?;
+    let p1 = negate(backend, &kzg_quotient)?;
+
+    // 13) fold in the recursion pairing-point accumulator
+    aggregate_pairing_points(backend, &p0, &p1, &proof.pairing_point_object())
+}
+
+/// Small-subgroup IPA consistency check tying the Libra evaluations to the
+/// claimed masked sumcheck evaluation.
+fn check_evals_consistency(
+    libra_poly_evals: &[Fr; LIBRA_EVALUATIONS],
+    gemini_r: Fr,
+    u_challenges: &[Fr; CONST_PROOF_SIZE_LOG_N],
+    libra_eval: Fr,
+) -> Result<(), &'static str> {
+    let one = Fr::one();
+    let vanishing_poly_eval = gemini_r.pow(SUBGROUP_SIZE as u128) - one;
+    if vanishing_poly_eval.is_zero() {
+        return Err("shplemini: gemini challenge in subgroup");
+    }
+
+    // Denominators g^{-i}·r - 1 for the Lagrange basis over H
+    let g_inv = SUBGROUP_GENERATOR_INVERSE;
+    let mut denominators = [Fr::zero(); SUBGROUP_SIZE];
+    let mut root_power = one;
+    for d in denominators.iter_mut() {
+        *d = root_power * gemini_r - one;
+        root_power = root_power * g_inv;
+    }
+    let mut inv_denominators = [Fr::zero(); SUBGROUP_SIZE];
+    batch_inverse(&denominators, &mut inv_denominators)
+        .map_err(|_| "shplemini: libra lagrange denominator is zero")?;
+
+    // Challenge polynomial in Lagrange form: (1, 1, u_0, u_0^2, .., 1, u_1, ..)
+    let mut challenge_poly_eval = inv_denominators[0];
+    for (round, u) in u_challenges.iter().enumerate() {
+        let curr = 1 + LIBRA_UNIVARIATES_LENGTH * round;
+        let mut coeff = one;
+        for inv in &inv_denominators[curr..curr + LIBRA_UNIVARIATES_LENGTH] {
+            challenge_poly_eval = challenge_poly_eval + coeff * *inv;
+            coeff = coeff * *u;
+        }
+    }
+
+    let numerator = vanishing_poly_eval
+        * Fr::from_u64(SUBGROUP_SIZE as u64)
+            .inverse()
+            .ok_or("shplemini: subgroup size not invertible")?;
+    challenge_poly_eval = challenge_poly_eval * numerator;
+    let lagrange_first = inv_denominators[0] * numerator;
+    let lagrange_last = inv_denominators[SUBGROUP_SIZE - 1] * numerator;
+
+    let mut diff = lagrange_first * libra_poly_evals[2];
+    diff = diff
+        + (gemini_r - g_inv)
+            * (libra_poly_evals[1]
+                - libra_poly_evals[2]
+                - libra_poly_evals[0] * challenge_poly_eval);
+    diff = diff + lagrange_last * (libra_poly_evals[2] - libra_eval)
+        - vanishing_poly_eval * libra_poly_evals[3];
+
+    if diff.is_zero() {
         Ok(())
     } else {
-        Err("Shplonk pairing check failed")
+        Err("shplemini: libra consistency check failed")
+    }
+}
+
+/// Shplemini verification for the ZK flavor
+pub fn verify_zk_shplemini<F: Flavor, B: Backend, D: ByteBuf>(
+    backend: &B,
+    proof: &ProofView<D>,
+    vk: &VerificationKey,
+    tp: &Transcript,
+    msm: MsmBackend,
+) -> Result<(), &'static str> {
+    let (p0, p1) = zk_shplemini_pairing_points::<F, B, D>(backend, proof, vk, tp, msm)?;
+    settle(backend, &p0, &p1)
+}
+
+/// ZK-flavor counterpart of [`shplemini_pairing_points`].
+pub fn zk_shplemini_pairing_points<F: Flavor, B: Backend, D: ByteBuf>(
+    backend: &B,
+    proof: &ProofView<D>,
+    vk: &VerificationKey,
+    tp: &Transcript,
+    msm: MsmBackend,
+) -> Result<(B::G1, B::G1), &'static str> {
+    // 1) r^{2^i}
+    let log_n = vk.log_circuit_size as usize;
+    let mut r_pows = [Fr::zero(); CONST_PROOF_SIZE_LOG_N];
+    r_pows[0] = tp.gemini_r;
+    for i in 1..log_n {
+        r_pows[i] = r_pows[i - 1] * r_pows[i - 1];
+    }
+
+    // Same batch layout as the non-ZK path, plus (z - g·r) for the shifted
+    // Libra grand-sum claim in the last slot.
+    const MAX_BATCH: usize = 3 * CONST_PROOF_SIZE_LOG_N + 2;
+    let batch_size = 3 + log_n + 2 * (log_n - 1) + 1;
+    let mut to_invert = [Fr::zero(); MAX_BATCH];
+    let mut inverted = [Fr::zero(); MAX_BATCH];
+
+    to_invert[0] = tp.shplonk_z - r_pows[0];
+    to_invert[1] = tp.shplonk_z + r_pows[0];
+    to_invert[2] = tp.gemini_r;
+
+    for j in (1..=log_n).rev() {
+        let u = tp.sumcheck_u_challenges[j - 1];
+        to_invert[3 + (log_n - j)] = r_pows[j - 1] * (Fr::one() - u) + u;
+    }
+
+    let further_base = 3 + log_n;
+    for j in 1..log_n {
+        to_invert[further_base + 2 * (j - 1)] = tp.shplonk_z - r_pows[j];
+        to_invert[further_base + 2 * (j - 1) + 1] = tp.shplonk_z + r_pows[j];
+    }
+    to_invert[batch_size - 1] = tp.shplonk_z - SUBGROUP_GENERATOR * tp.gemini_r;
+
+    batch_inverse(&to_invert[..batch_size], &mut inverted[..batch_size]).map_err(|_| {
+        "shplemini: batch inversion failed (zero denominator in shplonk/gemini/fold)"
+    })?;
+
+    let pos0 = inverted[0];
+    let neg0 = inverted[1];
+    let gemini_r_inv = inverted[2];
+    let shifted_libra_inv = inverted[batch_size - 1];
+
+    // 2) allocate arrays, sized for the largest flavor and round count
+    // Layout, with E = F::NUMBER_OF_ENTITIES, R = proof.rounds() and
+    // L = LIBRA_COMMITMENTS:
+    //   [0]                 = shplonk_Q
+    //   [1]                 = gemini masking polynomial
+    //   [2..E+2)            = VK + proof entities
+    //   [E+2..E+R+1)        = gemini_fold_comms (R - 1, only the first
+    //                         log_n - 1 are read)
+    //   [E+R+1..E+R+L+1)    = libra commitments
+    //   [E+R+L+1]           = generator (1,2) with const_acc scalar
+    //   [E+R+L+2]           = kzg_quotient with scalar z
+    const TOTAL: usize = NUMBER_OF_ENTITIES + CONST_PROOF_SIZE_LOG_N + LIBRA_COMMITMENTS + 3;
+    let rounds = proof.rounds();
+    let mut scalars = [Fr::zero(); TOTAL];
+    let mut coms = [G1Point::infinity(); TOTAL];
+
+    // 3) compute shplonk weights
+    let unshifted = pos0 + tp.shplonk_nu * neg0;
+    let shifted = gemini_r_inv * (pos0 - tp.shplonk_nu * neg0);
+
+    // 4) shplonk_Q
+    scalars[0] = Fr::one();
+    coms[0] = proof.shplonk_q();
+
+    // 5) masking polynomial takes rho^0, entities rho^1..
+    scalars[1] = -unshifted;
+    coms[1] = proof.gemini_masking_poly();
+    let mut rho_pow = tp.rho;
+    let mut eval_acc = proof.gemini_masking_eval();
+    let evaluations = proof.sumcheck_evaluations();
+    for (idx, eval) in evaluations[..F::NUMBER_OF_ENTITIES].iter().enumerate() {
+        let scalar = if idx < F::NUMBER_UNSHIFTED {
+            -unshifted
+        } else {
+            -shifted
+        } * rho_pow;
+        scalars[2 + idx] = scalar;
+        eval_acc = eval_acc + (*eval * rho_pow);
+        rho_pow = rho_pow * tp.rho;
     }
+
+    // 6) load VK & proof
+    load_entity_commitments::<F, D>(&mut coms, 2, vk, proof)?;
+
+    // 7) folding rounds
+    let a_evaluations = proof.gemini_a_evaluations();
+    let mut fold_pos = [Fr::zero(); CONST_PROOF_SIZE_LOG_N];
+    let mut cur = eval_acc;
+    for j in (1..=log_n).rev() {
+        let r2 = r_pows[j - 1];
+        let u = tp.sumcheck_u_challenges[j - 1];
+        let num = r2 * cur * Fr::from_u64(2) - a_evaluations[j - 1] * (r2 * (Fr::one() - u) - u);
+        let den_inv = inverted[3 + (log_n - j)];
+        cur = num * den_inv;
+        fold_pos[j - 1] = cur;
+    }
+
+    // 8) accumulate constant term
+    let mut const_acc = fold_pos[0] * pos0 + a_evaluations[0] * tp.shplonk_nu * neg0;
+    let mut v_pow = tp.shplonk_nu * tp.shplonk_nu;
+
+    // 9) further folding + commit
+    let base = 2 + F::NUMBER_OF_ENTITIES;
+    for j in 1..rounds {
+        if j < log_n {
+            let pos_inv = inverted[further_base + 2 * (j - 1)];
+            let neg_inv = inverted[further_base + 2 * (j - 1) + 1];
+            let sp = v_pow * pos_inv;
+            let sn = v_pow * tp.shplonk_nu * neg_inv;
+
+            scalars[base + j - 1] = -(sp + sn);
+            const_acc = const_acc + a_evaluations[j] * sn + fold_pos[j] * sp;
+            coms[base + j - 1] = proof.gemini_fold_comm(j - 1);
+        }
+        // The nu power keeps running through the dummy rounds so the Libra
+        // claims below start at nu^{2·rounds}.
+        v_pow = v_pow * tp.shplonk_nu * tp.shplonk_nu;
+    }
+
+    // 10) libra claims: concatenation at r, grand sum at g·r and r, quotient at r
+    let libra_base = base + (rounds - 1);
+    let denominators = [pos0, shifted_libra_inv, pos0, pos0];
+    let libra_poly_evals = proof.libra_poly_evals();
+    let mut batching_scalars = [Fr::zero(); LIBRA_EVALUATIONS];
+    for i in 0..LIBRA_EVALUATIONS {
+        let scaling_factor = denominators[i] * v_pow;
+        batching_scalars[i] = -scaling_factor;
+        v_pow = v_pow * tp.shplonk_nu;
+        const_acc = const_acc + scaling_factor * libra_poly_evals[i];
+    }
+    scalars[libra_base] = batching_scalars[0];
+    scalars[libra_base + 1] = batching_scalars[1] + batching_scalars[2];
+    scalars[libra_base + 2] = batching_scalars[3];
+    coms[libra_base..libra_base + LIBRA_COMMITMENTS].copy_from_slice(&proof.libra_commitments());
+
+    // 11) add generator
+    let one_idx = libra_base + LIBRA_COMMITMENTS;
+    coms[one_idx] = G1Point::generator();
+    scalars[one_idx] = const_acc;
+
+    check_evals_consistency(
+        &libra_poly_evals,
+        tp.gemini_r,
+        &tp.sumcheck_u_challenges,
+        proof.libra_evaluation(),
+    )?;
+
+    // 12) add quotient
+    let q_idx = one_idx + 1;
+    let kzg_quotient = proof.kzg_quotient();
+    coms[q_idx] = kzg_quotient;
+    scalars[q_idx] = tp.shplonk_z;
+
+    // 13) MSM over the compacted terms, as in the non-ZK path
+    let n = merge_msm_terms(&mut coms, &mut scalars)?;
+    let p0 = g1_msm(backend, msm, &coms[..n], &scalars[..n])?;
+    let p1 = negate(backend, &kzg_quotient)?;
+
+    // 14) fold in the recursion pairing-point accumulator
+    aggregate_pairing_points(backend, &p0, &p1, &proof.pairing_point_object())
 }
@@ -1,53 +1,13 @@
 //! Sum-check verifier
 use crate::{
+    backend::ByteBuf,
     field::{batch_inverse, Fr},
+    flavor::Flavor,
     relations::accumulate_relation_evaluations,
-    types::{Transcript, VerificationKey, BATCHED_RELATION_PARTIAL_LENGTH},
+    types::{Transcript, VerificationKey},
+    view::ProofView,
 };
 
-const BARY_BYTES: [[u8; 32]; BATCHED_RELATION_PARTIAL_LENGTH] = [
-    [
-        0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58,
-        0x5d, 0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xef, 0xff,
-        0xec, 0x51,
-    ],
-    [
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x02, 0xd0,
-    ],
-    [
-        0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58,
-        0x5d, 0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xef, 0xff,
-        0xff, 0x11,
-    ],
-    [
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x90,
-    ],
-    [
-        0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58,
-        0x5d, 0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xef, 0xff,
-        0xff, 0x71,
-    ],
-    [
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0xf0,
-    ],
-    [
-        0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58,
-        0x5d, 0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xef, 0xff,
-        0xfd, 0x31,
-    ],
-    [
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x13, 0xb0,
-    ],
-];
-
 /// Check if the sum of two univariates equals the target value
 #[inline(always)]
 fn check_sum(round_univariate: &[Fr], round_target: Fr) -> bool {
@@ -56,32 +16,33 @@ fn check_sum(round_univariate: &[Fr], round_target: Fr) -> bool {
 }
 
 /// Calculate next target value for the sum-check using batch inversion.
-/// Instead of 8 individual inversions per round, uses Montgomery's trick
-/// to compute all 8 with a single inversion + 21 multiplications.
+/// Instead of N individual inversions per round, uses Montgomery's trick
+/// to compute all N with a single inversion + 3(N-1) multiplications.
 #[inline(always)]
-fn compute_next_target_sum(
-    round_univariate: &[Fr],
+fn compute_next_target_sum<F: Flavor>(
+    round_univariate: &F::Univariate,
     round_challenge: Fr,
 ) -> Result<Fr, &'static str> {
-    // B(χ) = ∏ (χ - i) for i in 0..8
+    let bary = F::BARYCENTRIC;
+    // B(χ) = ∏ (χ - i) for i in 0..N
     // Also collect denominators for batch inversion
-    let mut denoms = [Fr::zero(); BATCHED_RELATION_PARTIAL_LENGTH];
+    let mut denoms = F::Univariate::default();
     let mut b_poly = Fr::one();
-    for i in 0..BATCHED_RELATION_PARTIAL_LENGTH {
+    for (i, (d, b)) in denoms.as_mut().iter_mut().zip(bary.as_ref()).enumerate() {
         let diff = round_challenge - Fr::from_u64(i as u64);
         b_poly = b_poly * diff;
-        denoms[i] = Fr::from_bytes(&BARY_BYTES[i]) * diff;
+        *d = *b * diff;
     }
 
-    // Batch invert all 8 denominators with a single Fr::inverse()
-    let mut inv_denoms = [Fr::zero(); BATCHED_RELATION_PARTIAL_LENGTH];
-    batch_inverse(&denoms, &mut inv_denoms)
+    // Batch invert all N denominators with a single Fr::inverse()
+    let mut inv_denoms = F::Univariate::default();
+    batch_inverse(denoms.as_ref(), inv_denoms.as_mut())
         .map_err(|_| "sumcheck: barycentric denominator is zero")?;
 
     // Σ u_i * inv_denom_i
     let mut acc = Fr::zero();
-    for i in 0..BATCHED_RELATION_PARTIAL_LENGTH {
-        acc = acc + (round_univariate[i] * inv_denoms[i]);
+    for (u, inv) in round_univariate.as_ref().iter().zip(inv_denoms.as_ref()) {
+        acc = acc + (*u * *inv);
     }
 
     Ok(b_poly * acc)
@@ -96,35 +57,51 @@ fn partially_evaluate_pow(
     pow_partial_evaluation * (Fr::one() + round_challenge * (gate_challenge - Fr::one()))
 }
 
-pub fn verify_sumcheck(
-    proof: &crate::types::Proof,
+/// Check the first `log_n` rounds, each univariate against the running
+/// target, and return the final target and pow evaluation.
+fn sumcheck_rounds<F: Flavor, D: ByteBuf>(
+    proof: &ProofView<D>,
     tp: &Transcript,
-    vk: &VerificationKey,
-) -> Result<(), &'static str> {
-    let log_n = vk.log_circuit_size as usize;
-    let mut round_target = Fr::zero();
+    log_n: usize,
+    initial_target: Fr,
+) -> Result<(Fr, Fr), &'static str> {
+    debug_assert_eq!(F::BATCHED_RELATION_PARTIAL_LENGTH, proof.univariate_len());
+    let mut round_target = initial_target;
     let mut pow_partial_evaluation = Fr::one();
 
-    // 1) Each round sum check and next target/pow calculation
     for round in 0..log_n {
-        let round_univariate = &proof.sumcheck_univariates[round];
+        let mut round_univariate = F::Univariate::default();
+        proof.read_univariate(round, round_univariate.as_mut());
 
-        if !check_sum(round_univariate, round_target) {
+        if !check_sum(round_univariate.as_ref(), round_target) {
             return Err("round failed");
         }
 
         let round_challenge = tp.sumcheck_u_challenges[round];
-        round_target = compute_next_target_sum(round_univariate, round_challenge)?;
+        round_target = compute_next_target_sum::<F>(&round_univariate, round_challenge)?;
         pow_partial_evaluation = partially_evaluate_pow(
             tp.gate_challenges[round],
             pow_partial_evaluation,
             round_challenge,
         );
     }
+    Ok((round_target, pow_partial_evaluation))
+}
+
+pub fn verify_sumcheck<F: Flavor, D: ByteBuf>(
+    proof: &ProofView<D>,
+    tp: &Transcript,
+    vk: &VerificationKey,
+) -> Result<(), &'static str> {
+    let log_n = vk.log_circuit_size as usize;
+
+    // 1) Each round sum check and next target/pow calculation
+    let (round_target, pow_partial_evaluation) =
+        sumcheck_rounds::<F, D>(proof, tp, log_n, Fr::zero())?;
 
     // 2) Final relation summation
-    let grand_honk_relation_sum = accumulate_relation_evaluations(
-        &proof.sumcheck_evaluations,
+    let grand_honk_relation_sum = accumulate_relation_evaluations::<F>(
+        &proof.sumcheck_evaluations(),
         &tp.rel_params,
         &tp.alphas,
         pow_partial_evaluation,
@@ -147,3 +124,49 @@ pub fn verify_sumcheck(
         Err("sumcheck final mismatch")
     }
 }
+
+/// ZK sum-check: the initial target is the Libra-masked claim, each round
+/// univariate has the ZK flavor's coefficient count, and the final
+/// relation sum is scaled by the row-disabling polynomial before adding the
+/// Libra evaluation.
+pub fn verify_zk_sumcheck<F: Flavor, D: ByteBuf>(
+    proof: &ProofView<D>,
+    tp: &Transcript,
+    vk: &VerificationKey,
+) -> Result<(), &'static str> {
+    let log_n = vk.log_circuit_size as usize;
+    let initial_target = tp.libra_challenge * proof.libra_sum();
+
+    // 1) Each round sum check and next target/pow calculation
+    let (round_target, pow_partial_evaluation) =
+        sumcheck_rounds::<F, D>(proof, tp, log_n, initial_target)?;
+
+    // 2) Final relation summation
+    let grand_honk_relation_sum = accumulate_relation_evaluations::<F>(
+        &proof.sumcheck_evaluations(),
+        &tp.rel_params,
+        &tp.alphas,
+        pow_partial_evaluation,
+    );
+
+    // 3) Row-disabling polynomial 1 - ∏_{i>=2} u_i, plus the Libra claim
+    let mut evaluation = Fr::one();
+    for i in 2..log_n {
+        evaluation = evaluation * tp.sumcheck_u_challenges[i];
+    }
+    let grand_honk_relation_sum = grand_honk_relation_sum * (Fr::one() - evaluation)
+        + proof.libra_evaluation() * tp.libra_challenge;
+
+    if grand_honk_relation_sum == round_target {
+        Ok(())
+    } else {
+        crate::trace!("===== ZK SUMCHECK FINAL CHECK FAILED =====");
+        crate::trace!(
+            "grand_relation = 0x{}",
+            hex::encode(grand_honk_relation_sum.to_bytes())
+        );
+        crate::trace!("target = 0x{}", hex::encode(round_target.to_bytes()));
+        crate::trace!("=========================================");
+        Err("sumcheck final mismatch")
+    }
+}
@@ -2,26 +2,18 @@
 
 use crate::trace;
 use crate::{
-    field::Fr,
-    hash::hash32,
+    backend::{Backend, ByteBuf},
+    field::{reduce_be, Fr},
+    flavor::Flavor,
+    hash::TranscriptHasher,
     types::{
-        G1Point, Proof, RelationParameters, Transcript, CONST_PROOF_SIZE_LOG_N, NUMBER_OF_ALPHAS,
+        RelationParameters, Transcript, CONST_PROOF_SIZE_LOG_N, IPA_CLAIM_SIZE, NUMBER_OF_ALPHAS,
+        PAIRING_POINTS_SIZE,
     },
-    utils::coord_to_halves_be,
+    view::{ProofView, WitnessCommitment},
 };
-use soroban_sdk::{Bytes, Env};
 
-fn push_point(buf: &mut Bytes, pt: &G1Point) {
-    // Serialize a coordinate into two bn254::Fr limbs (lo136, hi<=118)
-    let (x_lo, x_hi) = coord_to_halves_be(&pt.x);
-    let (y_lo, y_hi) = coord_to_halves_be(&pt.y);
-    buf.extend_from_slice(&x_lo);
-    buf.extend_from_slice(&x_hi);
-    buf.extend_from_slice(&y_lo);
-    buf.extend_from_slice(&y_hi);
-}
-
-fn split_challenge(challenge: Fr) -> (Fr, Fr) {
+pub(crate) fn split_challenge(challenge: Fr) -> (Fr, Fr) {
     let challenge_bytes = challenge.to_bytes();
     let mut low_bytes = [0u8; 32];
     low_bytes[16..].copy_from_slice(&challenge_bytes[16..]);
@@ -31,269 +23,304 @@ fn split_challenge(challenge: Fr) -> (Fr, Fr) {
 }
 
 #[inline(always)]
-fn hash_to_fr(bytes: &Bytes) -> Fr {
-    Fr::from_bytes(&hash32(bytes))
+pub(crate) fn hash_to_fr<B: Backend, H: TranscriptHasher>(
+    backend: &B,
+    hasher: &H,
+    bytes: &B::Bytes,
+) -> Result<Fr, &'static str> {
+    let digest = hasher.hash(backend, bytes).ok_or(UNSUPPORTED_HASH)?;
+    Ok(Fr::from_bytes(&digest))
 }
 
+/// Error for an oracle hash the backend cannot compute.
+pub(crate) const UNSUPPORTED_HASH: &str = "oracle hash not supported by this backend";
+
 fn u64_to_be32(x: u64) -> [u8; 32] {
     let mut out = [0u8; 32];
     out[24..].copy_from_slice(&x.to_be_bytes());
     out
 }
 
-fn generate_eta_challenge(
-    env: &Env,
-    proof: &Proof,
-    public_inputs: &Bytes,
-    circuit_size: u64,
-    public_inputs_size: u64,
-    pub_inputs_offset: u64,
-) -> (Fr, Fr, Fr, Fr) {
-    let mut data = Bytes::new(env);
-    data.extend_from_slice(&u64_to_be32(circuit_size));
-    data.extend_from_slice(&u64_to_be32(public_inputs_size));
-    data.extend_from_slice(&u64_to_be32(pub_inputs_offset));
-    data.append(public_inputs);
-    for fr in &proof.pairing_point_object {
-        data.extend_from_slice(&fr.to_bytes());
-    }
-    for w in &[&proof.w1, &proof.w2, &proof.w3] {
-        push_point(&mut data, w);
-    }
-
-    let previous_challenge = hash_to_fr(&data);
-    let (eta, eta_two) = split_challenge(previous_challenge);
-    let prev_bytes = Bytes::from_array(env, &previous_challenge.to_bytes());
-    let previous_challenge = hash_to_fr(&prev_bytes);
-    let (eta_three, _) = split_challenge(previous_challenge);
-
-    (eta, eta_two, eta_three, previous_challenge)
+/// Challenge from 16 big-endian bytes of a reduced hash output.
+#[inline(always)]
+fn half_to_fr(half: &[u8]) -> Fr {
+    let mut word = [0u8; 16];
+    word.copy_from_slice(half);
+    Fr::from_u128(u128::from_be_bytes(word))
 }
 
-fn generate_beta_and_gamma_challenges(
-    env: &Env,
-    previous_challenge: Fr,
-    proof: &Proof,
-) -> (Fr, Fr, Fr) {
-    let mut data = Bytes::new(env);
-    data.extend_from_slice(&previous_challenge.to_bytes());
-    for w in &[
-        &proof.lookup_read_counts,
-        &proof.lookup_read_tags,
-        &proof.w4,
-    ] {
-        push_point(&mut data, w);
-    }
-    let next_previous_challenge = hash_to_fr(&data);
-    let (beta, gamma) = split_challenge(next_previous_challenge);
-    (beta, gamma, next_previous_challenge)
+/// Fiat–Shamir state: the previous challenge, kept as the canonical
+/// big-endian bytes it is hashed as, and the hash input being built.
+/// Proof sections are appended as serialized; every field the verifier
+/// later decodes has been checked canonical, so this matches re-encoding
+/// the decoded values.
+pub struct FiatShamir<'a, B: Backend, H: TranscriptHasher> {
+    backend: &'a B,
+    hasher: &'a H,
+    buf: B::Bytes,
+    challenge: [u8; 32],
 }
 
-fn generate_alpha_challenges(
-    env: &Env,
-    previous_challenge: Fr,
-    proof: &Proof,
-) -> ([Fr; NUMBER_OF_ALPHAS], Fr) {
-    let mut data = Bytes::new(env);
-    data.extend_from_slice(&previous_challenge.to_bytes());
-    for w in &[&proof.lookup_inverses, &proof.z_perm] {
-        push_point(&mut data, w);
+impl<'a, B: Backend, H: TranscriptHasher> FiatShamir<'a, B, H> {
+    pub fn new(backend: &'a B, hasher: &'a H) -> Self {
+        Self {
+            backend,
+            hasher,
+            buf: backend.bytes(),
+            challenge: [0u8; 32],
+        }
     }
-    let mut next_previous_challenge = hash_to_fr(&data);
 
-    let mut alphas = [Fr::zero(); NUMBER_OF_ALPHAS];
-    let (a0, a1) = split_challenge(next_previous_challenge);
-    alphas[0] = a0;
-    alphas[1] = a1;
+    /// Derive the challenges of one `F` proof. The state is reset first, so
+    /// one `FiatShamir` serves any number of proofs.
+    pub fn transcript<F: Flavor>(
+        &mut self,
+        proof: &ProofView<B::Bytes>,
+        ipa_claim: &[Fr],
+        public_inputs: &B::Bytes,
+        circuit_size: u64,
+        public_inputs_size: u64,
+        pub_inputs_offset: u64,
+    ) -> Result<Transcript, &'static str> {
+        self.challenge = [0u8; 32];
 
-    for i in 1..(NUMBER_OF_ALPHAS / 2) {
-        let next_bytes = Bytes::from_array(env, &next_previous_challenge.to_bytes());
-        next_previous_challenge = hash_to_fr(&next_bytes);
-        let (lo, hi) = split_challenge(next_previous_challenge);
-        alphas[2 * i] = lo;
-        alphas[2 * i + 1] = hi;
-    }
+        // 1) eta/beta/gamma
+        let rel_params = self.relation_parameters(
+            proof,
+            ipa_claim,
+            public_inputs,
+            circuit_size,
+            public_inputs_size,
+            pub_inputs_offset,
+        )?;
+
+        // 2) alphas
+        let alphas = self.alphas(proof, F::NUMBER_OF_ALPHAS)?;
+
+        // 3) gate challenges
+        let gate_challenges = self.repeated(proof.rounds())?;
 
-    if (NUMBER_OF_ALPHAS & 1) == 1 && NUMBER_OF_ALPHAS > 2 {
-        let next_bytes = Bytes::from_array(env, &next_previous_challenge.to_bytes());
-        next_previous_challenge = hash_to_fr(&next_bytes);
-        let (last, _) = split_challenge(next_previous_challenge);
-        alphas[NUMBER_OF_ALPHAS - 1] = last;
+        // 4) libra challenge (ZK only): concatenation commitment, libra sum
+        let libra_challenge = if proof.is_zk() {
+            let start = proof.witness_at(proof.witnesses());
+            self.section(proof, start, proof.univariates_at())?
+        } else {
+            Fr::zero()
+        };
+
+        // 5) sumcheck challenges, one per serialized round
+        let len = proof.univariate_len();
+        let mut sumcheck_u_challenges = [Fr::zero(); CONST_PROOF_SIZE_LOG_N];
+        for (r, u) in sumcheck_u_challenges
+            .iter_mut()
+            .take(proof.rounds())
+            .enumerate()
+        {
+            let start = proof.univariates_at() + r * len;
+            *u = self.section(proof, start, start + len)?;
+        }
+
+        // 6) rho: sumcheck evaluations, plus the Libra evaluation and
+        // commitments and the Gemini masking claim in the ZK flavor
+        let rho = self.section(proof, proof.evaluations_at(), proof.fold_comms_at())?;
+
+        // 7) gemini_r: fold commitments
+        let gemini_r = self.section(proof, proof.fold_comms_at(), proof.a_evaluations_at())?;
+
+        // 8) shplonk_nu: Gemini evaluations, plus the Libra polynomial
+        // evaluations in the ZK flavor
+        let shplonk_nu = self.section(proof, proof.a_evaluations_at(), proof.shplonk_q_at())?;
+
+        // 9) shplonk_z: shplonk_q
+        let q = proof.shplonk_q_at();
+        let shplonk_z = self.section(proof, q, q + proof.format().point_words())?;
+
+        trace!("===== TRANSCRIPT PARAMETERS =====");
+        trace!("eta = 0x{}", hex::encode(rel_params.eta.to_bytes()));
+        trace!("eta_two = 0x{}", hex::encode(rel_params.eta_two.to_bytes()));
+        trace!(
+            "eta_three = 0x{}",
+            hex::encode(rel_params.eta_three.to_bytes())
+        );
+        trace!("beta = 0x{}", hex::encode(rel_params.beta.to_bytes()));
+        trace!("gamma = 0x{}", hex::encode(rel_params.gamma.to_bytes()));
+        trace!(
+            "libra_challenge = 0x{}",
+            hex::encode(libra_challenge.to_bytes())
+        );
+        trace!("rho = 0x{}", hex::encode(rho.to_bytes()));
+        trace!("gemini_r = 0x{}", hex::encode(gemini_r.to_bytes()));
+        trace!("shplonk_nu = 0x{}", hex::encode(shplonk_nu.to_bytes()));
+        trace!("shplonk_z = 0x{}", hex::encode(shplonk_z.to_bytes()));
+        trace!("circuit_size = {}", circuit_size);
+        trace!("public_inputs_total = {}", public_inputs_size);
+        trace!("public_inputs_offset = {}", pub_inputs_offset);
+        trace!("=================================");
+
+        Ok(Transcript {
+            rel_params,
+            alphas,
+            gate_challenges,
+            libra_challenge,
+            sumcheck_u_challenges,
+            rho,
+            gemini_r,
+            shplonk_nu,
+            shplonk_z,
+        })
     }
 
-    (alphas, next_previous_challenge)
-}
+    /// Hash the buffer into the next challenge and return its low and high
+    /// 128-bit halves. The digest is reduced mod r on its bytes, so chaining
+    /// rounds needs no conversion to and from `Fr`.
+    fn squeeze(&mut self) -> Result<(Fr, Fr), &'static str> {
+        let digest = self
+            .hasher
+            .hash(self.backend, &self.buf)
+            .ok_or(UNSUPPORTED_HASH)?;
+        self.challenge = reduce_be(&digest);
+        Ok((
+            half_to_fr(&self.challenge[16..]),
+            half_to_fr(&self.challenge[..16]),
+        ))
+    }
 
-fn generate_relation_parameters_challenges(
-    env: &Env,
-    proof: &Proof,
-    public_inputs: &Bytes,
-    circuit_size: u64,
-    public_inputs_size: u64,
-    pub_inputs_offset: u64,
-) -> (RelationParameters, Fr) {
-    let (eta, eta_two, eta_three, previous_challenge) = generate_eta_challenge(
-        env,
-        proof,
-        public_inputs,
-        circuit_size,
-        public_inputs_size,
-        pub_inputs_offset,
-    );
-    let (beta, gamma, next_previous_challenge) =
-        generate_beta_and_gamma_challenges(env, previous_challenge, proof);
-    let rp = RelationParameters {
-        eta,
-        eta_two,
-        eta_three,
-        beta,
-        gamma,
-        public_inputs_delta: Fr::zero(),
-    };
-    (rp, next_previous_challenge)
-}
+    /// Restart the hash input from the previous challenge.
+    fn chain(&mut self) {
+        self.buf.reset(&self.challenge);
+    }
 
-fn generate_gate_challenges(
-    env: &Env,
-    previous_challenge: Fr,
-) -> ([Fr; CONST_PROOF_SIZE_LOG_N], Fr) {
-    let mut next_previous_challenge = previous_challenge;
-    let mut gate_challenges = [Fr::zero(); CONST_PROOF_SIZE_LOG_N];
-    for i in 0..CONST_PROOF_SIZE_LOG_N {
-        let next_bytes = Bytes::from_array(env, &next_previous_challenge.to_bytes());
-        next_previous_challenge = hash_to_fr(&next_bytes);
-        gate_challenges[i] = split_challenge(next_previous_challenge).0;
+    /// Previous challenge followed by proof words `start..end`.
+    fn section(
+        &mut self,
+        proof: &ProofView<B::Bytes>,
+        start: usize,
+        end: usize,
+    ) -> Result<Fr, &'static str> {
+        self.chain();
+        self.buf.append(&proof.raw(start, end - start));
+        Ok(self.squeeze()?.0)
     }
-    (gate_challenges, next_previous_challenge)
-}
 
-fn generate_sumcheck_challenges(
-    env: &Env,
-    proof: &Proof,
-    previous_challenge: Fr,
-) -> ([Fr; CONST_PROOF_SIZE_LOG_N], Fr) {
-    let mut next_previous_challenge = previous_challenge;
-    let mut sumcheck_challenges = [Fr::zero(); CONST_PROOF_SIZE_LOG_N];
-    for r in 0..CONST_PROOF_SIZE_LOG_N {
-        let mut data = Bytes::new(env);
-        data.extend_from_slice(&next_previous_challenge.to_bytes());
-        for &c in proof.sumcheck_univariates[r].iter() {
-            data.extend_from_slice(&c.to_bytes());
+    /// `n` challenges, each the hash of the previous one alone.
+    fn repeated(&mut self, n: usize) -> Result<[Fr; CONST_PROOF_SIZE_LOG_N], &'static str> {
+        let mut out = [Fr::zero(); CONST_PROOF_SIZE_LOG_N];
+        for c in out.iter_mut().take(n) {
+            self.chain();
+            *c = self.squeeze()?.0;
         }
-        next_previous_challenge = hash_to_fr(&data);
-        sumcheck_challenges[r] = split_challenge(next_previous_challenge).0;
+        Ok(out)
     }
-    (sumcheck_challenges, next_previous_challenge)
-}
 
-fn generate_rho_challenge(env: &Env, proof: &Proof, previous_challenge: Fr) -> (Fr, Fr) {
-    let mut data = Bytes::new(env);
-    data.extend_from_slice(&previous_challenge.to_bytes());
-    for &e in proof.sumcheck_evaluations.iter() {
-        data.extend_from_slice(&e.to_bytes());
-    }
-    let next_previous_challenge = hash_to_fr(&data);
-    let rho = split_challenge(next_previous_challenge).0;
-    (rho, next_previous_challenge)
-}
+    fn relation_parameters(
+        &mut self,
+        proof: &ProofView<B::Bytes>,
+        ipa_claim: &[Fr],
+        public_inputs: &B::Bytes,
+        circuit_size: u64,
+        public_inputs_size: u64,
+        pub_inputs_offset: u64,
+    ) -> Result<RelationParameters, &'static str> {
+        let p = proof.format().point_words();
 
-fn generate_gemini_r_challenge(env: &Env, proof: &Proof, previous_challenge: Fr) -> (Fr, Fr) {
-    let mut data = Bytes::new(env);
-    data.extend_from_slice(&previous_challenge.to_bytes());
-    for pt in proof.gemini_fold_comms.iter() {
-        push_point(&mut data, pt);
+        // eta, eta_two: VK metadata, public inputs (the pairing point object
+        // and any IPA claim last), w1, w2, w3
+        self.buf.reset(&u64_to_be32(circuit_size));
+        self.buf.extend_from_slice(&u64_to_be32(public_inputs_size));
+        self.buf.extend_from_slice(&u64_to_be32(pub_inputs_offset));
+        self.buf.append(public_inputs);
+        self.buf.append(&proof.raw(0, PAIRING_POINTS_SIZE));
+        for fr in ipa_claim {
+            self.buf.extend_from_slice(&fr.to_bytes());
+        }
+        let w1 = proof.witness_at(WitnessCommitment::W1 as usize);
+        self.buf.append(&proof.raw(w1, 3 * p));
+        let (eta, eta_two) = self.squeeze()?;
+        self.chain();
+        let (eta_three, _) = self.squeeze()?;
+
+        // beta, gamma: lookup_read_counts, lookup_read_tags, w4
+        let start = proof.witness_at(WitnessCommitment::LookupReadCounts as usize);
+        self.chain();
+        self.buf.append(&proof.raw(start, 3 * p));
+        let (beta, gamma) = self.squeeze()?;
+
+        Ok(RelationParameters {
+            eta,
+            eta_two,
+            eta_three,
+            beta,
+            gamma,
+            public_inputs_delta: Fr::zero(),
+        })
     }
-    let next_previous_challenge = hash_to_fr(&data);
-    let gemini_r = split_challenge(next_previous_challenge).0;
-    (gemini_r, next_previous_challenge)
-}
 
-fn generate_shplonk_nu_challenge(env: &Env, proof: &Proof, previous_challenge: Fr) -> (Fr, Fr) {
-    let mut data = Bytes::new(env);
-    data.extend_from_slice(&previous_challenge.to_bytes());
-    for &a in proof.gemini_a_evaluations.iter() {
-        data.extend_from_slice(&a.to_bytes());
+    /// `n` alphas in (low, high) pairs: the first from lookup_inverses and
+    /// z_perm, the rest by rehashing.
+    fn alphas(
+        &mut self,
+        proof: &ProofView<B::Bytes>,
+        n: usize,
+    ) -> Result<[Fr; NUMBER_OF_ALPHAS], &'static str> {
+        let start = proof.witness_at(WitnessCommitment::LookupInverses as usize);
+        self.chain();
+        self.buf
+            .append(&proof.raw(start, 2 * proof.format().point_words()));
+        let mut alphas = [Fr::zero(); NUMBER_OF_ALPHAS];
+        (alphas[0], alphas[1]) = self.squeeze()?;
+        for i in 1..(n / 2) {
+            self.chain();
+            (alphas[2 * i], alphas[2 * i + 1]) = self.squeeze()?;
+        }
+        if (n & 1) == 1 && n > 2 {
+            self.chain();
+            alphas[n - 1] = self.squeeze()?.0;
+        }
+        Ok(alphas)
     }
-    let next_previous_challenge = hash_to_fr(&data);
-    let shplonk_nu = split_challenge(next_previous_challenge).0;
-    (shplonk_nu, next_previous_challenge)
 }
 
-fn generate_shplonk_z_challenge(env: &Env, proof: &Proof, previous_challenge: Fr) -> (Fr, Fr) {
-    let mut data = Bytes::new(env);
-    data.extend_from_slice(&previous_challenge.to_bytes());
-    push_point(&mut data, &proof.shplonk_q);
-    let next_previous_challenge = hash_to_fr(&data);
-    let shplonk_z = split_challenge(next_previous_challenge).0;
-    (shplonk_z, next_previous_challenge)
+/// Fiat–Shamir transcript of an `F` proof. The ZK flavor adds the Libra
+/// challenge after the gate challenges and absorbs its extra claims into the
+/// rho and shplonk_nu rounds.
+pub fn generate_transcript<F: Flavor, B: Backend, H: TranscriptHasher>(
+    backend: &B,
+    hasher: &H,
+    proof: &ProofView<B::Bytes>,
+    public_inputs: &B::Bytes,
+    circuit_size: u64,
+    public_inputs_size: u64,
+    pub_inputs_offset: u64,
+) -> Result<Transcript, &'static str> {
+    FiatShamir::new(backend, hasher).transcript::<F>(
+        proof,
+        &[],
+        public_inputs,
+        circuit_size,
+        public_inputs_size,
+        pub_inputs_offset,
+    )
 }
 
-pub fn generate_transcript(
-    env: &Env,
-    proof: &Proof,
-    public_inputs: &Bytes,
+/// Fiat–Shamir transcript for UltraRollupHonk: the IPA claim is absorbed
+/// as public inputs right after the pairing point object.
+#[allow(clippy::too_many_arguments)]
+pub fn generate_rollup_transcript<F: Flavor, B: Backend, H: TranscriptHasher>(
+    backend: &B,
+    hasher: &H,
+    proof: &ProofView<B::Bytes>,
+    ipa_claim: &[Fr; IPA_CLAIM_SIZE],
+    public_inputs: &B::Bytes,
     circuit_size: u64,
     public_inputs_size: u64,
     pub_inputs_offset: u64,
-) -> Transcript {
-    // 1) eta/beta/gamma
-    let (rp, previous_challenge) = generate_relation_parameters_challenges(
-        env,
+) -> Result<Transcript, &'static str> {
+    FiatShamir::new(backend, hasher).transcript::<F>(
         proof,
+        ipa_claim,
         public_inputs,
         circuit_size,
         public_inputs_size,
         pub_inputs_offset,
-    );
-
-    // 2) alphas
-    let (alphas, previous_challenge) = generate_alpha_challenges(env, previous_challenge, proof);
-
-    // 3) gate challenges
-    let (gate_chals, previous_challenge) = generate_gate_challenges(env, previous_challenge);
-
-    // 4) sumcheck challenges
-    let (u_chals, previous_challenge) =
-        generate_sumcheck_challenges(env, proof, previous_challenge);
-
-    // 5) rho
-    let (rho, previous_challenge) = generate_rho_challenge(env, proof, previous_challenge);
-
-    // 6) gemini_r
-    let (gemini_r, previous_challenge) =
-        generate_gemini_r_challenge(env, proof, previous_challenge);
-
-    // 7) shplonk_nu
-    let (shplonk_nu, previous_challenge) =
-        generate_shplonk_nu_challenge(env, proof, previous_challenge);
-
-    // 8) shplonk_z
-    let (shplonk_z, _previous_challenge) =
-        generate_shplonk_z_challenge(env, proof, previous_challenge);
-
-    trace!("===== TRANSCRIPT PARAMETERS =====");
-    trace!("eta = 0x{}", hex::encode(rp.eta.to_bytes()));
-    trace!("eta_two = 0x{}", hex::encode(rp.eta_two.to_bytes()));
-    trace!("eta_three = 0x{}", hex::encode(rp.eta_three.to_bytes()));
-    trace!("beta = 0x{}", hex::encode(rp.beta.to_bytes()));
-    trace!("gamma = 0x{}", hex::encode(rp.gamma.to_bytes()));
-    trace!("rho = 0x{}", hex::encode(rho.to_bytes()));
-    trace!("gemini_r = 0x{}", hex::encode(gemini_r.to_bytes()));
-    trace!("shplonk_nu = 0x{}", hex::encode(shplonk_nu.to_bytes()));
-    trace!("shplonk_z = 0x{}", hex::encode(shplonk_z.to_bytes()));
-    trace!("circuit_size = {}", circuit_size);
-    trace!("public_inputs_total = {}", public_inputs_size);
-    trace!("public_inputs_offset = {}", pub_inputs_offset);
-    trace!("=================================");
-
-    Transcript {
-        rel_params: rp,
-        alphas,
-        gate_challenges: gate_chals,
-        sumcheck_u_challenges: u_chals,
-        rho,
-        gemini_r,
-        shplonk_nu,
-        shplonk_z,
-    }
+    )
 }
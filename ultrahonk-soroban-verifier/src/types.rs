@@ -1,13 +1,64 @@
 use crate::field::Fr;
+use crate::flavor::UltraFlavor;
+use crate::format::{ProofFormat, VkFormat, VK_NUM_POINTS};
+use crate::utils::coord_to_halves_be;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
 
 pub const CONST_PROOF_SIZE_LOG_N: usize = 28;
+// Ultra flavor counts, and the capacity of the buffers a `Flavor` fills
 pub const NUMBER_OF_SUBRELATIONS: usize = 26;
 pub const BATCHED_RELATION_PARTIAL_LENGTH: usize = 8;
+pub const ZK_BATCHED_RELATION_PARTIAL_LENGTH: usize = 9;
 pub const NUMBER_OF_ENTITIES: usize = 40;
 pub const NUMBER_UNSHIFTED: usize = 35;
 pub const NUMBER_TO_BE_SHIFTED: usize = 5;
 pub const PAIRING_POINTS_SIZE: usize = 16;
 pub const NUMBER_OF_ALPHAS: usize = NUMBER_OF_SUBRELATIONS - 1;
+// ZK (Libra / small-subgroup IPA) parameters
+pub const LIBRA_COMMITMENTS: usize = 3;
+pub const LIBRA_EVALUATIONS: usize = 4;
+pub const LIBRA_UNIVARIATES_LENGTH: usize = 9;
+pub const SUBGROUP_SIZE: usize = 256;
+// UltraRollupHonk (IPA accumulation) parameters
+pub const CONST_ECCVM_LOG_N: usize = 16;
+pub const IPA_CLAIM_SIZE: usize = 10;
+pub const IPA_PROOF_LENGTH: usize = 4 * CONST_ECCVM_LOG_N + 5;
+
+/// Why a proof, VK or field encoding could not be decoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// Byte length matches no supported layout.
+    WrongLength,
+    /// VK header fields are malformed or out of range.
+    BadHeader,
+    /// A G1 point does not satisfy y² = x³ + 3.
+    PointNotOnCurve,
+    /// A VK commitment that can never be zero is the point at infinity.
+    PointAtInfinity,
+    /// A scalar is ≥ r (or a hex literal is malformed).
+    NonCanonicalScalar,
+    /// A base field coordinate is ≥ q or a limb exceeds its bit width.
+    NonCanonicalCoordinate,
+    /// A padded proof's unused rounds differ, so it has no compact encoding.
+    IrregularPadding,
+}
+
+/// Which VerificationKey metadata invariant failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VkError {
+    /// `log_circuit_size` is 0 (no sumcheck rounds).
+    LogCircuitSizeZero,
+    /// `log_circuit_size` exceeds `CONST_PROOF_SIZE_LOG_N`.
+    LogCircuitSizeTooLarge,
+    /// `circuit_size != 2^log_circuit_size`.
+    CircuitSizeMismatch,
+    /// `public_inputs_size` cannot hold the pairing point object.
+    TooFewPublicInputs,
+    /// The public inputs at `pub_inputs_offset` do not fit in the circuit.
+    PublicInputsOutOfRange,
+}
 
 /// Wire indices for the Ultra Honk protocol.
 #[derive(Copy, Clone, Debug)]
@@ -112,45 +163,72 @@ impl Default for G1Point {
 /// The verification key structure
 #[derive(Clone, Debug)]
 pub struct VerificationKey {
+    // Serialization layout the VK was decoded from
+    pub format: VkFormat,
     pub circuit_size: u64,
     pub log_circuit_size: u64,
     pub public_inputs_size: u64,
-    // Selectors and wire commitments:
-    pub qm: G1Point,
-    pub qc: G1Point,
-    pub ql: G1Point,
-    pub qr: G1Point,
-    pub qo: G1Point,
-    pub q4: G1Point,
-    pub q_lookup: G1Point,
-    pub q_arith: G1Point,
-    pub q_delta_range: G1Point,
-    pub q_elliptic: G1Point,
-    pub q_aux: G1Point,
-    pub q_poseidon2_external: G1Point,
-    pub q_poseidon2_internal: G1Point,
-    // Copy constraints:
-    pub s1: G1Point,
-    pub s2: G1Point,
-    pub s3: G1Point,
-    pub s4: G1Point,
-    pub id1: G1Point,
-    pub id2: G1Point,
-    pub id3: G1Point,
-    pub id4: G1Point,
-    // Lookup table commitments:
-    pub t1: G1Point,
-    pub t2: G1Point,
-    pub t3: G1Point,
-    pub t4: G1Point,
-    // Fixed first/last
-    pub lagrange_first: G1Point,
-    pub lagrange_last: G1Point,
+    // Row of the first public input in the execution trace
+    pub pub_inputs_offset: u64,
+    // Precomputed commitments, indexed by `Wire`
+    pub commitments: [G1Point; VK_NUM_POINTS],
+}
+
+impl VerificationKey {
+    /// The commitment to a precomputed polynomial, `None` for witness wires.
+    pub fn commitment(&self, w: Wire) -> Option<&G1Point> {
+        self.commitments.get(w.index())
+    }
+
+    /// Serialize in `self.format`; the inverse of
+    /// [`load_vk_from_bytes`](crate::utils::load_vk_from_bytes).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.format.vk_bytes());
+        match self.format {
+            VkFormat::V0_87 => {
+                for h in [
+                    self.circuit_size,
+                    self.log_circuit_size,
+                    self.public_inputs_size,
+                    self.pub_inputs_offset,
+                ] {
+                    out.extend_from_slice(&h.to_be_bytes());
+                }
+            }
+        }
+        for &w in self.format.commitments() {
+            out.extend_from_slice(&self.commitments[w.index()].to_bytes());
+        }
+        out
+    }
+
+    /// Check the header metadata the verifier indexes with.
+    pub fn validate(&self) -> Result<(), VkError> {
+        if self.log_circuit_size == 0 {
+            return Err(VkError::LogCircuitSizeZero);
+        }
+        if self.log_circuit_size > CONST_PROOF_SIZE_LOG_N as u64 {
+            return Err(VkError::LogCircuitSizeTooLarge);
+        }
+        if self.circuit_size != 1u64 << self.log_circuit_size {
+            return Err(VkError::CircuitSizeMismatch);
+        }
+        if self.public_inputs_size < PAIRING_POINTS_SIZE as u64 {
+            return Err(VkError::TooFewPublicInputs);
+        }
+        match self.pub_inputs_offset.checked_add(self.public_inputs_size) {
+            Some(end) if end <= self.circuit_size => {}
+            _ => return Err(VkError::PublicInputsOutOfRange),
+        }
+        Ok(())
+    }
 }
 
 /// The Proof structure
 #[derive(Clone, Debug)]
 pub struct Proof {
+    // Serialization layout the proof was decoded from
+    pub format: ProofFormat,
     // Pairing point object (16 Fr elements)
     pub pairing_point_object: [Fr; PAIRING_POINTS_SIZE],
     // Wire commitments
@@ -174,6 +252,108 @@ pub struct Proof {
     pub kzg_quotient: G1Point,
 }
 
+impl Proof {
+    /// Serialize in `self.format` for a circuit of size 2^`log_n`; the
+    /// inverse of [`load_proof_with_format`](crate::utils::load_proof_with_format).
+    pub fn to_bytes(&self, log_n: usize) -> Vec<u8> {
+        let format = self.format;
+        let rounds = format.rounds(log_n);
+        let mut out = Vec::with_capacity(format.proof_bytes::<UltraFlavor>(log_n));
+        let push_frs = |out: &mut Vec<u8>, frs: &[Fr]| {
+            for fr in frs {
+                out.extend_from_slice(&fr.to_bytes());
+            }
+        };
+
+        push_frs(&mut out, &self.pairing_point_object);
+        for pt in [
+            &self.w1,
+            &self.w2,
+            &self.w3,
+            &self.lookup_read_counts,
+            &self.lookup_read_tags,
+            &self.w4,
+            &self.lookup_inverses,
+            &self.z_perm,
+        ] {
+            push_proof_point(&mut out, pt, format);
+        }
+        for row in &self.sumcheck_univariates[..rounds] {
+            push_frs(&mut out, row);
+        }
+        push_frs(&mut out, &self.sumcheck_evaluations);
+        for pt in &self.gemini_fold_comms[..rounds - 1] {
+            push_proof_point(&mut out, pt, format);
+        }
+        push_frs(&mut out, &self.gemini_a_evaluations[..rounds]);
+        push_proof_point(&mut out, &self.shplonk_q, format);
+        push_proof_point(&mut out, &self.kzg_quotient, format);
+        out
+    }
+}
+
+/// A proof commitment in `format`: (x_lo, x_hi, y_lo, y_hi) limbs for
+/// `V0_87`.
+fn push_proof_point(out: &mut Vec<u8>, pt: &G1Point, format: ProofFormat) {
+    match format {
+        ProofFormat::V0_87 => {
+            for coord in [&pt.x, &pt.y] {
+                let (lo, hi) = coord_to_halves_be(coord);
+                out.extend_from_slice(&lo);
+                out.extend_from_slice(&hi);
+            }
+        }
+    }
+}
+
+/// UltraRollupHonk proof (`bb prove --ipa_accumulation`): an UltraHonk proof
+/// plus the Grumpkin IPA claim it carries as public inputs and the IPA
+/// opening proof appended after it.
+#[derive(Clone, Debug)]
+pub struct RollupProof {
+    pub proof: Proof,
+    // Opening claim: challenge (4 limbs), evaluation (4 limbs), commitment (x, y)
+    pub ipa_claim: [Fr; IPA_CLAIM_SIZE],
+    // poly_length, (L, R) per round, G_0, a_0 (lo, hi)
+    pub ipa_proof: [Fr; IPA_PROOF_LENGTH],
+}
+
+/// The ZK Proof structure (UltraKeccakZKFlavor, `bb prove --zk`)
+#[derive(Clone, Debug)]
+pub struct ZkProof {
+    // Serialization layout the proof was decoded from
+    pub format: ProofFormat,
+    // Pairing point object (16 Fr elements)
+    pub pairing_point_object: [Fr; PAIRING_POINTS_SIZE],
+    // Wire commitments
+    pub w1: G1Point,
+    pub w2: G1Point,
+    pub w3: G1Point,
+    pub w4: G1Point,
+    // Lookup helpers
+    pub lookup_read_counts: G1Point,
+    pub lookup_read_tags: G1Point,
+    pub lookup_inverses: G1Point,
+    pub z_perm: G1Point,
+    // Libra: concatenation, grand sum and quotient commitments
+    pub libra_commitments: [G1Point; LIBRA_COMMITMENTS],
+    pub libra_sum: Fr,
+    // Sumcheck polynomials
+    pub sumcheck_univariates: [[Fr; ZK_BATCHED_RELATION_PARTIAL_LENGTH]; CONST_PROOF_SIZE_LOG_N],
+    pub sumcheck_evaluations: [Fr; NUMBER_OF_ENTITIES],
+    pub libra_evaluation: Fr,
+    // Gemini masking
+    pub gemini_masking_poly: G1Point,
+    pub gemini_masking_eval: Fr,
+    // Gemini fold commitments
+    pub gemini_fold_comms: [G1Point; CONST_PROOF_SIZE_LOG_N - 1],
+    pub gemini_a_evaluations: [Fr; CONST_PROOF_SIZE_LOG_N],
+    pub libra_poly_evals: [Fr; LIBRA_EVALUATIONS],
+    // Shplonk
+    pub shplonk_q: G1Point,
+    pub kzg_quotient: G1Point,
+}
+
 /// Relation parameters (η, η₂, η₃, β, γ, public_inputs_delta).
 #[derive(Clone, Debug)]
 pub struct RelationParameters {
@@ -189,8 +369,11 @@ pub struct RelationParameters {
 #[derive(Clone, Debug)]
 pub struct Transcript {
     pub rel_params: RelationParameters,
+    /// The flavor's `NUMBER_OF_ALPHAS` challenges, zero-padded.
     pub alphas: [Fr; NUMBER_OF_ALPHAS],
     pub gate_challenges: [Fr; CONST_PROOF_SIZE_LOG_N],
+    /// Libra batching challenge (ZK flavor only, zero otherwise).
+    pub libra_challenge: Fr,
     pub sumcheck_u_challenges: [Fr; CONST_PROOF_SIZE_LOG_N],
     pub rho: Fr,
     pub gemini_r: Fr,
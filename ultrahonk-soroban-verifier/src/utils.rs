@@ -1,17 +1,21 @@
 //! Utilities for loading Proof and VerificationKey, plus byte↔field/point conversion.
 
+use crate::backend::{Backend, ByteBuf};
+use crate::ec::{g1_is_on_curve, FQ_MODULUS_BE};
 use crate::field::Fr;
+use crate::flavor::{UltraFlavor, UltraZkFlavor};
+use crate::format::{ProofFormat, VkFormat, VK_NUM_POINTS};
 use crate::types::{
-    G1Point, Proof, VerificationKey, BATCHED_RELATION_PARTIAL_LENGTH, CONST_PROOF_SIZE_LOG_N,
-    NUMBER_OF_ENTITIES, PAIRING_POINTS_SIZE,
+    G1Point, ParseError, Proof, RelationParameters, RollupProof, Transcript, VerificationKey, Wire,
+    ZkProof, CONST_PROOF_SIZE_LOG_N, IPA_CLAIM_SIZE, IPA_PROOF_LENGTH, NUMBER_OF_ALPHAS,
+    PAIRING_POINTS_SIZE,
 };
-use crate::PROOF_BYTES;
-use core::array;
-use soroban_sdk::Bytes;
+use crate::view::ProofView;
+use crate::{PROOF_BYTES, ROLLUP_PROOF_BYTES, ZK_PROOF_BYTES};
 
-/// Convert a 32-byte big-endian array into an Fr.
-fn bytes32_to_fr(bytes: &[u8; 32]) -> Fr {
-    Fr::from_bytes(bytes)
+/// Convert a 32-byte big-endian array into an Fr, rejecting values ≥ r.
+fn bytes32_to_fr(bytes: &[u8; 32]) -> Result<Fr, ParseError> {
+    Fr::from_bytes_canonical(bytes).ok_or(ParseError::NonCanonicalScalar)
 }
 
 /// Split a 32-byte big-endian field element into (low136, high) limbs.
@@ -23,192 +27,274 @@ pub fn coord_to_halves_be(coord: &[u8; 32]) -> ([u8; 32], [u8; 32]) {
     (low, high)
 }
 
-fn read_bytes<const N: usize>(bytes: &Bytes, idx: &mut u32) -> [u8; N] {
+fn read_bytes<const N: usize>(bytes: &impl ByteBuf, idx: &mut u32) -> [u8; N] {
     let mut out = [0u8; N];
     let end = *idx + N as u32;
-    bytes.slice(*idx..end).copy_into_slice(&mut out);
+    bytes.read_into(*idx, &mut out);
     *idx = end;
     out
 }
 
-fn combine_limbs(lo: &[u8; 32], hi: &[u8; 32]) -> [u8; 32] {
+/// Recombine (lo136, hi) limbs into a 32-byte big-endian value. Limbs with
+/// bits outside their 136 / 120-bit window are rejected, not truncated.
+pub(crate) fn combine_limbs(lo: &[u8; 32], hi: &[u8; 32]) -> Result<[u8; 32], ParseError> {
+    if lo[..15].iter().chain(&hi[..17]).any(|&b| b != 0) {
+        return Err(ParseError::NonCanonicalCoordinate);
+    }
+    Ok(join_limbs(lo, hi))
+}
+
+/// [`combine_limbs`] for limbs already known to be in range.
+pub(crate) fn join_limbs(lo: &[u8; 32], hi: &[u8; 32]) -> [u8; 32] {
     let mut out = [0u8; 32];
     out[..15].copy_from_slice(&hi[17..]);
     out[15..].copy_from_slice(&lo[15..]);
     out
 }
 
+/// Validate a decoded G1 point: canonical coordinates and on the curve
+/// unless it is the point at infinity (0, 0).
+pub(crate) fn check_g1_point(x: [u8; 32], y: [u8; 32]) -> Result<G1Point, ParseError> {
+    if x >= FQ_MODULUS_BE || y >= FQ_MODULUS_BE {
+        return Err(ParseError::NonCanonicalCoordinate);
+    }
+    let pt = G1Point { x, y };
+    if pt != G1Point::infinity() && !g1_is_on_curve(&pt) {
+        return Err(ParseError::PointNotOnCurve);
+    }
+    Ok(pt)
+}
+
+// Helper: bytesToFr (read next 32 bytes as Fr)
+fn bytes_to_fr(bytes: &impl ByteBuf, cur: &mut u32) -> Result<Fr, ParseError> {
+    let arr = read_bytes::<32>(bytes, cur);
+    bytes32_to_fr(&arr)
+}
+
+fn read_fr_array<const N: usize>(
+    bytes: &impl ByteBuf,
+    cur: &mut u32,
+) -> Result<[Fr; N], ParseError> {
+    let mut out = [Fr::zero(); N];
+    for f in out.iter_mut() {
+        *f = bytes_to_fr(bytes, cur)?;
+    }
+    Ok(out)
+}
+
 /// Load a Proof from a byte array.
 ///
 /// Note (bb v0.87.0): G1 coordinates are encoded as two limbs per coordinate
 /// using the (lo136, hi<=118) split and stored in the order (x_lo, x_hi, y_lo, y_hi).
-pub fn load_proof(proof_bytes: &Bytes) -> Proof {
-    assert_eq!(proof_bytes.len() as usize, PROOF_BYTES, "proof bytes len");
-    let mut boundary = 0u32;
-
-    fn bytes_to_g1_proof_point(bytes: &Bytes, cur: &mut u32) -> G1Point {
-        let x0 = read_bytes::<32>(bytes, cur);
-        let x1 = read_bytes::<32>(bytes, cur);
-        let y0 = read_bytes::<32>(bytes, cur);
-        let y1 = read_bytes::<32>(bytes, cur);
-        let x = combine_limbs(&x0, &x1);
-        let y = combine_limbs(&y0, &y1);
-        G1Point { x, y }
+pub fn load_proof(proof_bytes: &impl ByteBuf) -> Result<Proof, ParseError> {
+    if proof_bytes.len() as usize != PROOF_BYTES {
+        return Err(ParseError::WrongLength);
     }
+    load_proof_with_format(proof_bytes, ProofFormat::V0_87, CONST_PROOF_SIZE_LOG_N)
+}
+
+/// Load a Proof serialized in `format` for a circuit of size 2^`log_n`,
+/// decoded through a checked [`ProofView`].
+pub fn load_proof_with_format(
+    proof_bytes: &impl ByteBuf,
+    format: ProofFormat,
+    log_n: usize,
+) -> Result<Proof, ParseError> {
+    Ok(ProofView::new::<UltraFlavor>(proof_bytes, format, log_n)?.to_proof())
+}
 
-    // Helper: bytesToFr (read next 32 bytes as Fr)
-    fn bytes_to_fr(bytes: &Bytes, cur: &mut u32) -> Fr {
-        let arr = read_bytes::<32>(bytes, cur);
-        bytes32_to_fr(&arr)
+/// Load an UltraRollupHonk proof (bb v0.87.0 layout).
+///
+/// Layout: pairing point object, IPA claim, the rest of the UltraHonk proof,
+/// then the IPA opening proof.
+pub fn load_rollup_proof(proof_bytes: &impl ByteBuf) -> Result<RollupProof, ParseError> {
+    let (honk, ipa_claim, ipa_proof) = split_rollup_proof(proof_bytes)?;
+    Ok(RollupProof {
+        proof: load_proof(&honk)?,
+        ipa_claim,
+        ipa_proof,
+    })
+}
+
+/// Split an UltraRollupHonk proof into the UltraHonk proof bytes (pairing
+/// point object and the rest, rejoined) and the decoded IPA claim and
+/// opening proof.
+#[allow(clippy::type_complexity)]
+pub(crate) fn split_rollup_proof<D: ByteBuf>(
+    proof_bytes: &D,
+) -> Result<(D, [Fr; IPA_CLAIM_SIZE], [Fr; IPA_PROOF_LENGTH]), ParseError> {
+    if proof_bytes.len() as usize != ROLLUP_PROOF_BYTES {
+        return Err(ParseError::WrongLength);
     }
+    let ppo_end = (PAIRING_POINTS_SIZE * 32) as u32;
+    let claim_end = ppo_end + (IPA_CLAIM_SIZE * 32) as u32;
+    let ipa_start = proof_bytes.len() - (IPA_PROOF_LENGTH * 32) as u32;
+
+    let mut honk = proof_bytes.range(0, ppo_end);
+    honk.append(&proof_bytes.range(claim_end, ipa_start));
 
-    // 0) pairing point object
-    let pairing_point_object: [Fr; PAIRING_POINTS_SIZE] =
-        array::from_fn(|_| bytes_to_fr(proof_bytes, &mut boundary));
+    let mut boundary = ppo_end;
+    let ipa_claim: [Fr; IPA_CLAIM_SIZE] = read_fr_array(proof_bytes, &mut boundary)?;
+    let mut boundary = ipa_start;
+    let ipa_proof: [Fr; IPA_PROOF_LENGTH] = read_fr_array(proof_bytes, &mut boundary)?;
 
-    // 1) w1, w2, w3
-    let w1 = bytes_to_g1_proof_point(proof_bytes, &mut boundary);
-    let w2 = bytes_to_g1_proof_point(proof_bytes, &mut boundary);
-    let w3 = bytes_to_g1_proof_point(proof_bytes, &mut boundary);
+    Ok((honk, ipa_claim, ipa_proof))
+}
 
-    // 2) lookup_read_counts, lookup_read_tags
-    let lookup_read_counts = bytes_to_g1_proof_point(proof_bytes, &mut boundary);
-    let lookup_read_tags = bytes_to_g1_proof_point(proof_bytes, &mut boundary);
+/// Load a ZK Proof (`bb prove --zk`) from a byte array.
+///
+/// Same limb encoding as [`load_proof`]; the layout adds the Libra commitments
+/// and evaluations, the Gemini masking polynomial, and one extra coefficient
+/// per sumcheck univariate.
+pub fn load_zk_proof(proof_bytes: &impl ByteBuf) -> Result<ZkProof, ParseError> {
+    if proof_bytes.len() as usize != ZK_PROOF_BYTES {
+        return Err(ParseError::WrongLength);
+    }
+    load_zk_proof_with_format(proof_bytes, ProofFormat::V0_87, CONST_PROOF_SIZE_LOG_N)
+}
 
-    // 3) w4
-    let w4 = bytes_to_g1_proof_point(proof_bytes, &mut boundary);
+/// Load a ZK Proof serialized in `format` for a circuit of size 2^`log_n`,
+/// decoded through a checked [`ProofView`].
+pub fn load_zk_proof_with_format(
+    proof_bytes: &impl ByteBuf,
+    format: ProofFormat,
+    log_n: usize,
+) -> Result<ZkProof, ParseError> {
+    Ok(ProofView::new::<UltraZkFlavor>(proof_bytes, format, log_n)?.to_zk_proof())
+}
 
-    // 4) lookup_inverses, z_perm
-    let lookup_inverses = bytes_to_g1_proof_point(proof_bytes, &mut boundary);
-    let z_perm = bytes_to_g1_proof_point(proof_bytes, &mut boundary);
+/// Field elements in a serialized [`Transcript`].
+pub const TRANSCRIPT_FIELDS: usize =
+    6 + NUMBER_OF_ALPHAS + CONST_PROOF_SIZE_LOG_N + 1 + CONST_PROOF_SIZE_LOG_N + 4;
 
-    // 5) sumcheck_univariates
-    let mut sumcheck_univariates =
-        [[Fr::zero(); BATCHED_RELATION_PARTIAL_LENGTH]; CONST_PROOF_SIZE_LOG_N];
-    for r in 0..CONST_PROOF_SIZE_LOG_N {
-        for i in 0..BATCHED_RELATION_PARTIAL_LENGTH {
-            sumcheck_univariates[r][i] = bytes_to_fr(proof_bytes, &mut boundary);
-        }
+/// Serialize a Transcript as 32-byte big-endian words, in field order.
+pub fn transcript_to_bytes<B: Backend>(backend: &B, t: &Transcript) -> B::Bytes {
+    let rp = &t.rel_params;
+    let mut out = backend.bytes();
+    for fr in [rp.eta, rp.eta_two, rp.eta_three, rp.beta, rp.gamma]
+        .iter()
+        .chain([rp.public_inputs_delta].iter())
+        .chain(t.alphas.iter())
+        .chain(t.gate_challenges.iter())
+        .chain([t.libra_challenge].iter())
+        .chain(t.sumcheck_u_challenges.iter())
+        .chain([t.rho, t.gemini_r, t.shplonk_nu, t.shplonk_z].iter())
+    {
+        out.extend_from_slice(&fr.to_bytes());
     }
+    out
+}
 
-    // 6) sumcheck_evaluations
-    let sumcheck_evaluations: [Fr; NUMBER_OF_ENTITIES] =
-        array::from_fn(|_| bytes_to_fr(proof_bytes, &mut boundary));
-
-    // 7) gemini_fold_comms
-    let gemini_fold_comms: [G1Point; CONST_PROOF_SIZE_LOG_N - 1] =
-        array::from_fn(|_| bytes_to_g1_proof_point(proof_bytes, &mut boundary));
-
-    // 8) gemini_a_evaluations
-    let gemini_a_evaluations: [Fr; CONST_PROOF_SIZE_LOG_N] =
-        array::from_fn(|_| bytes_to_fr(proof_bytes, &mut boundary));
-
-    // 9) shplonk_q, kzg_quotient
-    let shplonk_q = bytes_to_g1_proof_point(proof_bytes, &mut boundary);
-    let kzg_quotient = bytes_to_g1_proof_point(proof_bytes, &mut boundary);
-
-    Proof {
-        pairing_point_object,
-        w1,
-        w2,
-        w3,
-        w4,
-        lookup_read_counts,
-        lookup_read_tags,
-        lookup_inverses,
-        z_perm,
-        sumcheck_univariates,
-        sumcheck_evaluations,
-        gemini_fold_comms,
-        gemini_a_evaluations,
-        shplonk_q,
-        kzg_quotient,
+/// Load a Transcript written by [`transcript_to_bytes`].
+pub fn load_transcript(bytes: &impl ByteBuf) -> Result<Transcript, ParseError> {
+    if bytes.len() as usize != TRANSCRIPT_FIELDS * 32 {
+        return Err(ParseError::WrongLength);
     }
+    let mut cur = 0u32;
+    let rel_params = RelationParameters {
+        eta: bytes_to_fr(bytes, &mut cur)?,
+        eta_two: bytes_to_fr(bytes, &mut cur)?,
+        eta_three: bytes_to_fr(bytes, &mut cur)?,
+        beta: bytes_to_fr(bytes, &mut cur)?,
+        gamma: bytes_to_fr(bytes, &mut cur)?,
+        public_inputs_delta: bytes_to_fr(bytes, &mut cur)?,
+    };
+    Ok(Transcript {
+        rel_params,
+        alphas: read_fr_array(bytes, &mut cur)?,
+        gate_challenges: read_fr_array(bytes, &mut cur)?,
+        libra_challenge: bytes_to_fr(bytes, &mut cur)?,
+        sumcheck_u_challenges: read_fr_array(bytes, &mut cur)?,
+        rho: bytes_to_fr(bytes, &mut cur)?,
+        gemini_r: bytes_to_fr(bytes, &mut cur)?,
+        shplonk_nu: bytes_to_fr(bytes, &mut cur)?,
+        shplonk_z: bytes_to_fr(bytes, &mut cur)?,
+    })
+}
+
+/// Load a VerificationKey, detecting its layout.
+pub fn load_vk_from_bytes(bytes: &impl ByteBuf) -> Result<VerificationKey, ParseError> {
+    let format = VkFormat::detect(bytes).ok_or(ParseError::WrongLength)?;
+    load_vk_with_format(bytes, format)
 }
 
-/// Load a VerificationKey.
-pub fn load_vk_from_bytes(bytes: &Bytes) -> Option<VerificationKey> {
-    const HEADER_WORDS: usize = 4;
-    const NUM_POINTS: usize = 27;
-    const EXPECTED_LEN: usize = HEADER_WORDS * 8 + NUM_POINTS * 64;
-    if bytes.len() as usize != EXPECTED_LEN {
-        return None;
+/// Load a VerificationKey serialized in `format`.
+///
+/// Every commitment must be canonical and on the curve; the permutation and
+/// Lagrange commitments must also not be the point at infinity.
+pub fn load_vk_with_format(
+    bytes: &impl ByteBuf,
+    format: VkFormat,
+) -> Result<VerificationKey, ParseError> {
+    if bytes.len() as usize != format.vk_bytes() {
+        return Err(ParseError::WrongLength);
     }
 
-    fn read_u64(bytes: &Bytes, idx: &mut u32) -> u64 {
+    fn read_u64(bytes: &impl ByteBuf, idx: &mut u32) -> u64 {
         u64::from_be_bytes(read_bytes::<8>(bytes, idx))
     }
-    fn read_point(bytes: &Bytes, idx: &mut u32) -> Option<G1Point> {
+    fn read_point(bytes: &impl ByteBuf, idx: &mut u32) -> Result<G1Point, ParseError> {
         let x = read_bytes::<32>(bytes, idx);
         let y = read_bytes::<32>(bytes, idx);
-        // Curve, subgroup checks are executed in the Soroban host.
-        Some(G1Point { x, y })
+        // Subgroup checks are executed in the Soroban host (G1 has cofactor 1).
+        check_g1_point(x, y)
+    }
+    fn read_nonzero_point(bytes: &impl ByteBuf, idx: &mut u32) -> Result<G1Point, ParseError> {
+        let pt = read_point(bytes, idx)?;
+        if pt == G1Point::infinity() {
+            return Err(ParseError::PointAtInfinity);
+        }
+        Ok(pt)
     }
 
     let mut idx = 0u32;
-    let circuit_size = read_u64(bytes, &mut idx);
-    let log_circuit_size = read_u64(bytes, &mut idx);
-    let public_inputs_size = read_u64(bytes, &mut idx);
-    let _pub_inputs_offset = read_u64(bytes, &mut idx);
-
-    let qm = read_point(bytes, &mut idx)?;
-    let qc = read_point(bytes, &mut idx)?;
-    let ql = read_point(bytes, &mut idx)?;
-    let qr = read_point(bytes, &mut idx)?;
-    let qo = read_point(bytes, &mut idx)?;
-    let q4 = read_point(bytes, &mut idx)?;
-    let q_lookup = read_point(bytes, &mut idx)?;
-    let q_arith = read_point(bytes, &mut idx)?;
-    let q_delta_range = read_point(bytes, &mut idx)?;
-    let q_elliptic = read_point(bytes, &mut idx)?;
-    let q_aux = read_point(bytes, &mut idx)?;
-    let q_poseidon2_external = read_point(bytes, &mut idx)?;
-    let q_poseidon2_internal = read_point(bytes, &mut idx)?;
-    let s1 = read_point(bytes, &mut idx)?;
-    let s2 = read_point(bytes, &mut idx)?;
-    let s3 = read_point(bytes, &mut idx)?;
-    let s4 = read_point(bytes, &mut idx)?;
-    let id1 = read_point(bytes, &mut idx)?;
-    let id2 = read_point(bytes, &mut idx)?;
-    let id3 = read_point(bytes, &mut idx)?;
-    let id4 = read_point(bytes, &mut idx)?;
-    let t1 = read_point(bytes, &mut idx)?;
-    let t2 = read_point(bytes, &mut idx)?;
-    let t3 = read_point(bytes, &mut idx)?;
-    let t4 = read_point(bytes, &mut idx)?;
-    let lagrange_first = read_point(bytes, &mut idx)?;
-    let lagrange_last = read_point(bytes, &mut idx)?;
-
-    Some(VerificationKey {
+    let (circuit_size, log_circuit_size, public_inputs_size, pub_inputs_offset) = match format {
+        VkFormat::V0_87 => {
+            let circuit_size = read_u64(bytes, &mut idx);
+            let log_circuit_size = read_u64(bytes, &mut idx);
+            let public_inputs_size = read_u64(bytes, &mut idx);
+            let pub_inputs_offset = read_u64(bytes, &mut idx);
+            (
+                circuit_size,
+                log_circuit_size,
+                public_inputs_size,
+                pub_inputs_offset,
+            )
+        }
+    };
+
+    let mut commitments = [G1Point::infinity(); VK_NUM_POINTS];
+    for &w in format.commitments() {
+        commitments[w.index()] = if never_infinity(w) {
+            read_nonzero_point(bytes, &mut idx)?
+        } else {
+            read_point(bytes, &mut idx)?
+        };
+    }
+
+    Ok(VerificationKey {
+        format,
         circuit_size,
         log_circuit_size,
         public_inputs_size,
-        qm,
-        qc,
-        ql,
-        qr,
-        qo,
-        q4,
-        q_lookup,
-        q_arith,
-        q_delta_range,
-        q_elliptic,
-        q_aux,
-        q_poseidon2_external,
-        q_poseidon2_internal,
-        s1,
-        s2,
-        s3,
-        s4,
-        id1,
-        id2,
-        id3,
-        id4,
-        t1,
-        t2,
-        t3,
-        t4,
-        lagrange_first,
-        lagrange_last,
+        pub_inputs_offset,
+        commitments,
     })
 }
+
+/// Permutation and Lagrange commitments, which no circuit leaves at zero.
+fn never_infinity(w: Wire) -> bool {
+    matches!(
+        w,
+        Wire::Sigma1
+            | Wire::Sigma2
+            | Wire::Sigma3
+            | Wire::Sigma4
+            | Wire::Id1
+            | Wire::Id2
+            | Wire::Id3
+            | Wire::Id4
+            | Wire::LagrangeFirst
+            | Wire::LagrangeLast
+    )
+}
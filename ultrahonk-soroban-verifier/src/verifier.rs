@@ -1,40 +1,104 @@
 //! UltraHonk verifier
 
 use crate::{
+    backend::{Backend, ByteBuf},
+    compact::expand_compact_proof,
+    ec::{batch_pairing_check, lhs_g2_affine, rhs_g2_affine, MsmBackend},
     field::Fr,
-    shplemini::verify_shplemini,
-    sumcheck::verify_sumcheck,
-    transcript::generate_transcript,
-    types::PAIRING_POINTS_SIZE,
-    utils::{load_proof, load_vk_from_bytes},
+    flavor::{Flavor, UltraFlavor, UltraZkFlavor},
+    format::ProofFormat,
+    grumpkin::GrumpkinPoint,
+    hash::OracleHash,
+    ipa::{verify_ipa, IpaClaim},
+    shplemini::{
+        shplemini_pairing_points, verify_shplemini, verify_zk_shplemini,
+        zk_shplemini_pairing_points,
+    },
+    sumcheck::{verify_sumcheck, verify_zk_sumcheck},
+    transcript::{generate_rollup_transcript, generate_transcript},
+    types::{ParseError, Transcript, VkError, IPA_CLAIM_SIZE, PAIRING_POINTS_SIZE},
+    utils::{load_vk_from_bytes, split_rollup_proof},
+    view::ProofView,
+    ROLLUP_PROOF_BYTES,
 };
-use soroban_sdk::{Bytes, Env};
+use soroban_sdk::Env;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
 
 /// Error type describing the specific reason verification failed.
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub enum VerifyError {
     InvalidInput(&'static str),
+    /// The proof, VK or public inputs could not be decoded.
+    Parse(ParseError),
+    /// The VK header metadata is inconsistent.
+    InvalidVk(VkError),
     SumcheckFailed(&'static str),
     ShplonkFailed(&'static str),
+    IpaFailed(&'static str),
+}
+
+/// The final KZG pairing of a verification, left unsettled:
+/// e(p0, rhs_g2)·e(p1, lhs_g2) == 1. The recursion accumulator of the proof
+/// is already folded into `p0`/`p1`.
+#[derive(Clone, Debug)]
+pub struct DeferredPairing<B: Backend = Env> {
+    pub p0: B::G1,
+    pub p1: B::G1,
+    pub rhs_g2: B::G2,
+    pub lhs_g2: B::G2,
 }
 
-pub struct UltraHonkVerifier {
-    env: Env,
+impl<B: Backend> DeferredPairing<B> {
+    /// Settle the pairing on its own.
+    pub fn check(&self, backend: &B) -> bool {
+        backend.pairing_check(
+            &[self.p0.clone(), self.p1.clone()],
+            &[self.rhs_g2.clone(), self.lhs_g2.clone()],
+        )
+    }
+}
+
+/// Verifier for one VK, running on the Soroban host by default or on any
+/// other [`Backend`].
+pub struct UltraHonkVerifier<B: Backend = Env> {
+    backend: B,
     vk: crate::types::VerificationKey,
+    oracle_hash: OracleHash,
+    msm: MsmBackend,
 }
 
-impl UltraHonkVerifier {
-    pub fn new_with_vk(env: &Env, vk: crate::types::VerificationKey) -> Self {
+impl<B: Backend> UltraHonkVerifier<B> {
+    pub fn new_with_vk(backend: &B, vk: crate::types::VerificationKey) -> Self {
         Self {
-            env: env.clone(),
+            backend: backend.clone(),
             vk,
+            oracle_hash: OracleHash::Keccak,
+            msm: MsmBackend::default(),
         }
     }
 
-    pub fn new(env: &Env, vk_bytes: &Bytes) -> Result<Self, VerifyError> {
-        load_vk_from_bytes(vk_bytes)
-            .map(|vk| Self::new_with_vk(env, vk))
-            .ok_or(VerifyError::InvalidInput("vk parse error"))
+    /// Select the transcript hash the proofs were generated with
+    /// (`bb prove --oracle_hash`). Defaults to Keccak.
+    pub fn with_oracle_hash(mut self, oracle_hash: OracleHash) -> Self {
+        self.oracle_hash = oracle_hash;
+        self
+    }
+
+    /// Select how the Shplemini MSM is evaluated. Defaults to
+    /// `MsmBackend::Windowed`.
+    pub fn with_msm_backend(mut self, msm: MsmBackend) -> Self {
+        self.msm = msm;
+        self
+    }
+
+    /// Parse and validate a VK. Rejects VKs whose metadata would make
+    /// verification index out of range.
+    pub fn new(backend: &B, vk_bytes: &B::Bytes) -> Result<Self, VerifyError> {
+        let vk = load_vk_from_bytes(vk_bytes).map_err(VerifyError::Parse)?;
+        vk.validate().map_err(VerifyError::InvalidVk)?;
+        Ok(Self::new_with_vk(backend, vk))
     }
 
     /// Expose a reference to the parsed VK for debugging/inspection.
@@ -42,47 +106,335 @@ impl UltraHonkVerifier {
         &self.vk
     }
 
-    /// Top-level verify
+    /// Detect the serialization layout and flavor of `proof_bytes` from its
+    /// length and the VK's circuit size.
+    pub fn detect_proof_format(&self, proof_bytes: &B::Bytes) -> Option<(ProofFormat, bool)> {
+        ProofFormat::detect(
+            proof_bytes.len() as usize,
+            self.vk.log_circuit_size as usize,
+        )
+    }
+
+    /// Top-level verify. Dispatches on the detected proof layout: plain
+    /// UltraKeccakFlavor or ZK, in any supported bb serialization.
     pub fn verify(
         &self,
-        proof_bytes: &Bytes,
-        public_inputs_bytes: &Bytes,
+        proof_bytes: &B::Bytes,
+        public_inputs_bytes: &B::Bytes,
     ) -> Result<(), VerifyError> {
-        // 1) parse proof
-        let proof = load_proof(proof_bytes);
+        // `new_with_vk` takes the VK as given
+        self.vk.validate().map_err(VerifyError::InvalidVk)?;
+        match self.detect_proof_format(proof_bytes) {
+            Some((format, false)) => self.verify_plain(proof_bytes, public_inputs_bytes, format),
+            Some((_, true)) => self.verify_zk(proof_bytes, public_inputs_bytes),
+            None => Err(VerifyError::Parse(ParseError::WrongLength)),
+        }
+    }
 
-        // 2) sanity on public inputs (length and VK metadata if present)
-        if public_inputs_bytes.len() % 32 != 0 {
+    fn verify_plain(
+        &self,
+        proof_bytes: &B::Bytes,
+        public_inputs_bytes: &B::Bytes,
+        format: ProofFormat,
+    ) -> Result<(), VerifyError> {
+        // 1) check the proof fields in use
+        let proof = self.view::<UltraFlavor>(proof_bytes, format)?;
+
+        // 2-4) public inputs, transcript, public delta
+        let t = self.transcript::<UltraFlavor>(&proof, public_inputs_bytes)?;
+
+        // 5) Sum-check
+        verify_sumcheck::<UltraFlavor, _>(&proof, &t, &self.vk)
+            .map_err(VerifyError::SumcheckFailed)?;
+
+        // 6) Shplonk
+        verify_shplemini::<UltraFlavor, _, _>(&self.backend, &proof, &self.vk, &t, self.msm)
+            .map_err(VerifyError::ShplonkFailed)?;
+
+        Ok(())
+    }
+
+    /// Verify a ZK proof (`bb prove --zk`).
+    pub fn verify_zk(
+        &self,
+        proof_bytes: &B::Bytes,
+        public_inputs_bytes: &B::Bytes,
+    ) -> Result<(), VerifyError> {
+        self.vk.validate().map_err(VerifyError::InvalidVk)?;
+        let format = match self.detect_proof_format(proof_bytes) {
+            Some((format, true)) => format,
+            _ => return Err(VerifyError::Parse(ParseError::WrongLength)),
+        };
+        // 1) check the proof fields in use
+        let proof = self.view::<UltraZkFlavor>(proof_bytes, format)?;
+
+        // 2-4) public inputs, transcript, public delta
+        let t = self.transcript::<UltraZkFlavor>(&proof, public_inputs_bytes)?;
+
+        // 5) Sum-check
+        verify_zk_sumcheck::<UltraZkFlavor, _>(&proof, &t, &self.vk)
+            .map_err(VerifyError::SumcheckFailed)?;
+
+        // 6) Shplonk
+        verify_zk_shplemini::<UltraZkFlavor, _, _>(&self.backend, &proof, &self.vk, &t, self.msm)
+            .map_err(VerifyError::ShplonkFailed)?;
+
+        Ok(())
+    }
+
+    /// Verify a plain proof in the [`compact`](crate::compact) encoding: the
+    /// bb v0.87.0 bytes are rebuilt, then verified as usual.
+    pub fn verify_compact(
+        &self,
+        compact_bytes: &B::Bytes,
+        public_inputs_bytes: &B::Bytes,
+    ) -> Result<(), VerifyError> {
+        self.vk.validate().map_err(VerifyError::InvalidVk)?;
+        let proof_bytes = expand_compact_proof(compact_bytes, self.vk.log_circuit_size as usize)
+            .map_err(VerifyError::Parse)?;
+        self.verify_plain(&proof_bytes, public_inputs_bytes, ProofFormat::V0_87)
+    }
+
+    /// Verify several proofs against this VK with a single pairing. Each
+    /// proof goes through its own transcript, sum-check and Shplemini; the
+    /// resulting (P0, P1) pairs are combined with powers of a challenge
+    /// hashed over every proof, its public inputs and its pairing inputs.
+    pub fn verify_batch(&self, proofs: &[(B::Bytes, B::Bytes)]) -> Result<(), VerifyError> {
+        if proofs.is_empty() {
+            return Err(VerifyError::InvalidInput("empty proof batch"));
+        }
+        let mut points = Vec::with_capacity(proofs.len());
+        let mut data = self.backend.bytes();
+        for (proof_bytes, public_inputs_bytes) in proofs {
+            let (p0, p1) = self.pairing_inputs(proof_bytes, public_inputs_bytes)?;
+            let mut statement = proof_bytes.clone();
+            statement.append(public_inputs_bytes);
+            data.extend_from_slice(&self.backend.keccak256(&statement));
+            data.extend_from_slice(&self.backend.g1_to_bytes(&p0));
+            data.extend_from_slice(&self.backend.g1_to_bytes(&p1));
+            points.push((p0, p1));
+        }
+        let rho = Fr::from_bytes(&self.backend.keccak256(&data));
+        if batch_pairing_check(&self.backend, &points, &rho) {
+            Ok(())
+        } else {
+            Err(VerifyError::ShplonkFailed("batched pairing check failed"))
+        }
+    }
+
+    /// Run the whole verification except the final pairing and return its
+    /// inputs, so callers can merge them into their own multi-pairing. The
+    /// proof is only valid once that pairing holds.
+    pub fn verify_deferred(
+        &self,
+        proof_bytes: &B::Bytes,
+        public_inputs_bytes: &B::Bytes,
+    ) -> Result<DeferredPairing<B>, VerifyError> {
+        let (p0, p1) = self.pairing_inputs(proof_bytes, public_inputs_bytes)?;
+        Ok(DeferredPairing {
+            p0,
+            p1,
+            rhs_g2: rhs_g2_affine(&self.backend),
+            lhs_g2: lhs_g2_affine(&self.backend),
+        })
+    }
+
+    /// Everything but the final pairing: the (P0, P1) inputs of one proof.
+    fn pairing_inputs(
+        &self,
+        proof_bytes: &B::Bytes,
+        public_inputs_bytes: &B::Bytes,
+    ) -> Result<(B::G1, B::G1), VerifyError> {
+        let proof = self.staged_view(proof_bytes)?;
+        let t = self.staged_transcript(&proof, public_inputs_bytes)?;
+        if proof.is_zk() {
+            verify_zk_sumcheck::<UltraZkFlavor, _>(&proof, &t, &self.vk)
+                .map_err(VerifyError::SumcheckFailed)?;
+            zk_shplemini_pairing_points::<UltraZkFlavor, _, _>(
+                &self.backend,
+                &proof,
+                &self.vk,
+                &t,
+                self.msm,
+            )
+        } else {
+            verify_sumcheck::<UltraFlavor, _>(&proof, &t, &self.vk)
+                .map_err(VerifyError::SumcheckFailed)?;
+            shplemini_pairing_points::<UltraFlavor, _, _>(
+                &self.backend,
+                &proof,
+                &self.vk,
+                &t,
+                self.msm,
+            )
+        }
+        .map_err(VerifyError::ShplonkFailed)
+    }
+
+    /// Stage 1 of a verification split across invocations: check the
+    /// proof encoding and the public inputs and derive the Fiat–Shamir transcript
+    /// (including the public-input delta). Persist the result with
+    /// [`crate::utils::transcript_to_bytes`].
+    pub fn stage_transcript(
+        &self,
+        proof_bytes: &B::Bytes,
+        public_inputs_bytes: &B::Bytes,
+    ) -> Result<Transcript, VerifyError> {
+        self.staged_transcript(&self.staged_view(proof_bytes)?, public_inputs_bytes)
+    }
+
+    /// Stage 2: sum-check against the stage 1 transcript of the same proof.
+    pub fn stage_sumcheck(
+        &self,
+        proof_bytes: &B::Bytes,
+        t: &Transcript,
+    ) -> Result<(), VerifyError> {
+        let proof = self.staged_view(proof_bytes)?;
+        if proof.is_zk() {
+            verify_zk_sumcheck::<UltraZkFlavor, _>(&proof, t, &self.vk)
+        } else {
+            verify_sumcheck::<UltraFlavor, _>(&proof, t, &self.vk)
+        }
+        .map_err(VerifyError::SumcheckFailed)
+    }
+
+    /// Stage 3: Shplemini batch opening and the final pairing.
+    pub fn stage_shplemini(
+        &self,
+        proof_bytes: &B::Bytes,
+        t: &Transcript,
+    ) -> Result<(), VerifyError> {
+        let proof = self.staged_view(proof_bytes)?;
+        if proof.is_zk() {
+            verify_zk_shplemini::<UltraZkFlavor, _, _>(&self.backend, &proof, &self.vk, t, self.msm)
+        } else {
+            verify_shplemini::<UltraFlavor, _, _>(&self.backend, &proof, &self.vk, t, self.msm)
+        }
+        .map_err(VerifyError::ShplonkFailed)
+    }
+
+    fn staged_view<'a>(
+        &self,
+        proof_bytes: &'a B::Bytes,
+    ) -> Result<ProofView<'a, B::Bytes>, VerifyError> {
+        self.vk.validate().map_err(VerifyError::InvalidVk)?;
+        match self.detect_proof_format(proof_bytes) {
+            Some((format, false)) => self.view::<UltraFlavor>(proof_bytes, format),
+            Some((format, true)) => self.view::<UltraZkFlavor>(proof_bytes, format),
+            None => Err(VerifyError::Parse(ParseError::WrongLength)),
+        }
+    }
+
+    fn view<'a, F: Flavor>(
+        &self,
+        proof_bytes: &'a B::Bytes,
+        format: ProofFormat,
+    ) -> Result<ProofView<'a, B::Bytes>, VerifyError> {
+        let log_n = self.vk.log_circuit_size as usize;
+        ProofView::new::<F>(proof_bytes, format, log_n).map_err(VerifyError::Parse)
+    }
+
+    /// [`Self::transcript`] for the flavor of a [`Self::staged_view`].
+    fn staged_transcript(
+        &self,
+        proof: &ProofView<B::Bytes>,
+        public_inputs_bytes: &B::Bytes,
+    ) -> Result<Transcript, VerifyError> {
+        if proof.is_zk() {
+            self.transcript::<UltraZkFlavor>(proof, public_inputs_bytes)
+        } else {
+            self.transcript::<UltraFlavor>(proof, public_inputs_bytes)
+        }
+    }
+
+    fn transcript<F: Flavor>(
+        &self,
+        proof: &ProofView<B::Bytes>,
+        public_inputs_bytes: &B::Bytes,
+    ) -> Result<Transcript, VerifyError> {
+        // sanity on public inputs (length and VK metadata if present)
+        let provided = self.check_public_inputs(public_inputs_bytes, PAIRING_POINTS_SIZE)?;
+
+        // Fiat–Shamir transcript
+        let pis_total = provided + PAIRING_POINTS_SIZE as u64;
+        let pub_inputs_offset = self.vk.pub_inputs_offset;
+        let mut t = generate_transcript::<F, _, _>(
+            &self.backend,
+            &self.oracle_hash,
+            proof,
+            public_inputs_bytes,
+            self.vk.circuit_size,
+            pis_total,
+            pub_inputs_offset,
+        )
+        .map_err(VerifyError::InvalidInput)?;
+
+        // Public delta
+        t.rel_params.public_inputs_delta = Self::compute_public_input_delta(
+            public_inputs_bytes,
+            &proof.pairing_point_object(),
+            t.rel_params.beta,
+            t.rel_params.gamma,
+            pub_inputs_offset,
+            self.vk.circuit_size,
+        )
+        .map_err(VerifyError::InvalidInput)?;
+        Ok(t)
+    }
+
+    /// Verify an UltraRollupHonk proof (`bb prove --ipa_accumulation
+    /// --oracle_hash poseidon2`): the honk proof with the IPA claim as extra
+    /// public inputs, then the Grumpkin IPA opening against `srs`.
+    ///
+    /// The IPA step needs the 2^16-point Grumpkin SRS and an MSM of that
+    /// size, so this is not meant to run inside a contract invocation.
+    pub fn verify_rollup(
+        &self,
+        proof_bytes: &B::Bytes,
+        public_inputs_bytes: &B::Bytes,
+        srs: &[GrumpkinPoint],
+    ) -> Result<(), VerifyError> {
+        self.vk.validate().map_err(VerifyError::InvalidVk)?;
+        if !OracleHash::Poseidon2.available::<B>() {
             return Err(VerifyError::InvalidInput(
-                "public inputs must be 32-byte aligned",
+                "oracle hash not supported by this backend",
             ));
         }
-        let provided = (public_inputs_bytes.len() / 32) as u64;
-        let expected = self
-            .vk
-            .public_inputs_size
-            .checked_sub(PAIRING_POINTS_SIZE as u64)
-            .ok_or(VerifyError::InvalidInput("vk inputs < 16"))?;
-        if expected != provided {
-            return Err(VerifyError::InvalidInput("public inputs mismatch"));
+        if proof_bytes.len() as usize != ROLLUP_PROOF_BYTES {
+            return Err(VerifyError::Parse(ParseError::WrongLength));
         }
+        // 1) split off the IPA claim and opening proof, check the honk proof
+        let (honk_bytes, ipa_claim, ipa_proof) =
+            split_rollup_proof(proof_bytes).map_err(VerifyError::Parse)?;
+        let proof = self.view::<UltraFlavor>(&honk_bytes, ProofFormat::V0_87)?;
+        let claim = IpaClaim::from_fields(&ipa_claim).map_err(VerifyError::InvalidInput)?;
+
+        // 2) sanity on public inputs (pairing points and IPA claim trail them)
+        let trailing = PAIRING_POINTS_SIZE + IPA_CLAIM_SIZE;
+        let provided = self.check_public_inputs(public_inputs_bytes, trailing)?;
 
         // 3) Fiat–Shamir transcript
-        let pis_total = provided + PAIRING_POINTS_SIZE as u64;
-        let pub_inputs_offset = 1;
-        let mut t = generate_transcript(
-            &self.env,
+        let pis_total = provided + trailing as u64;
+        let pub_inputs_offset = self.vk.pub_inputs_offset;
+        let mut t = generate_rollup_transcript::<UltraFlavor, _, _>(
+            &self.backend,
+            &self.oracle_hash,
             &proof,
+            &ipa_claim,
             public_inputs_bytes,
             self.vk.circuit_size,
             pis_total,
             pub_inputs_offset,
-        );
+        )
+        .map_err(VerifyError::InvalidInput)?;
 
         // 4) Public delta
+        let mut trailing_inputs = [Fr::zero(); PAIRING_POINTS_SIZE + IPA_CLAIM_SIZE];
+        trailing_inputs[..PAIRING_POINTS_SIZE].copy_from_slice(&proof.pairing_point_object());
+        trailing_inputs[PAIRING_POINTS_SIZE..].copy_from_slice(&ipa_claim);
         t.rel_params.public_inputs_delta = Self::compute_public_input_delta(
             public_inputs_bytes,
-            &proof.pairing_point_object,
+            &trailing_inputs,
             t.rel_params.beta,
             t.rel_params.gamma,
             pub_inputs_offset,
@@ -91,16 +443,60 @@ impl UltraHonkVerifier {
         .map_err(VerifyError::InvalidInput)?;
 
         // 5) Sum-check
-        verify_sumcheck(&proof, &t, &self.vk).map_err(VerifyError::SumcheckFailed)?;
+        verify_sumcheck::<UltraFlavor, _>(&proof, &t, &self.vk)
+            .map_err(VerifyError::SumcheckFailed)?;
 
         // 6) Shplonk
-        verify_shplemini(&self.env, &proof, &self.vk, &t).map_err(VerifyError::ShplonkFailed)?;
+        verify_shplemini::<UltraFlavor, _, _>(&self.backend, &proof, &self.vk, &t, self.msm)
+            .map_err(VerifyError::ShplonkFailed)?;
+
+        // 7) IPA opening
+        verify_ipa(&self.backend, &claim, &ipa_proof, srs).map_err(VerifyError::IpaFailed)?;
 
         Ok(())
     }
 
+    /// Check public inputs are 32-byte aligned, canonical field elements and
+    /// match the VK count (excluding the `trailing` proof-carried inputs such
+    /// as the pairing point object). Returns the number provided.
+    fn check_public_inputs(
+        &self,
+        public_inputs_bytes: &B::Bytes,
+        trailing: usize,
+    ) -> Result<u64, VerifyError> {
+        if !self.oracle_hash.available::<B>() {
+            return Err(VerifyError::InvalidInput(
+                "oracle hash not supported by this backend",
+            ));
+        }
+        if public_inputs_bytes.len() % 32 != 0 {
+            return Err(VerifyError::InvalidInput(
+                "public inputs must be 32-byte aligned",
+            ));
+        }
+        let provided = (public_inputs_bytes.len() / 32) as u64;
+        let expected = self
+            .vk
+            .public_inputs_size
+            .checked_sub(trailing as u64)
+            .ok_or(VerifyError::InvalidInput("vk inputs < trailing inputs"))?;
+        if expected != provided {
+            return Err(VerifyError::InvalidInput("public inputs mismatch"));
+        }
+        let mut idx = 0u32;
+        while idx < public_inputs_bytes.len() {
+            let mut arr = [0u8; 32];
+            public_inputs_bytes.read_into(idx, &mut arr);
+            if Fr::from_bytes_canonical(&arr).is_none() {
+                return Err(VerifyError::Parse(ParseError::NonCanonicalScalar));
+            }
+            idx += 32;
+        }
+        Ok(provided)
+    }
+
     fn compute_public_input_delta(
-        public_inputs: &Bytes,
+        public_inputs: &B::Bytes,
         pairing_point_object: &[Fr],
         beta: Fr,
         gamma: Fr,
@@ -116,7 +512,7 @@ impl UltraHonkVerifier {
         let mut idx = 0u32;
         while idx < public_inputs.len() {
             let mut arr = [0u8; 32];
-            public_inputs.slice(idx..idx + 32).copy_into_slice(&mut arr);
+            public_inputs.read_into(idx, &mut arr);
             let public_input = Fr::from_bytes(&arr);
             numerator = numerator * (numerator_acc + public_input);
             denominator = denominator * (denominator_acc + public_input);
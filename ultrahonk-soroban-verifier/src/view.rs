@@ -0,0 +1,410 @@
+//! Zero-copy view over serialized proof bytes.
+//!
+//! [`ProofView`] checks every word once, up front, and then decodes fields
+//! on access. The padding rounds of a `V0_87` proof are only hashed into
+//! the transcript, never decoded, but their scalars must still be below r
+//! and their limbs within range: Poseidon2 reduces each word mod r, so a
+//! second encoding of the same value would hash the same.
+
+use crate::backend::ByteBuf;
+use crate::ec::{pairing_points_to_g1, FQ_MODULUS_BE};
+use crate::field::{Fr, MODULUS_BE};
+use crate::flavor::Flavor;
+use crate::format::ProofFormat;
+use crate::types::{
+    G1Point, ParseError, Proof, ZkProof, CONST_PROOF_SIZE_LOG_N, LIBRA_COMMITMENTS,
+    LIBRA_EVALUATIONS, NUMBER_OF_ENTITIES, PAIRING_POINTS_SIZE,
+};
+use crate::utils::{check_g1_point, combine_limbs, join_limbs};
+
+/// Witness commitments in serialization order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WitnessCommitment {
+    W1 = 0,
+    W2 = 1,
+    W3 = 2,
+    LookupReadCounts = 3,
+    LookupReadTags = 4,
+    W4 = 5,
+    LookupInverses = 6,
+    ZPerm = 7,
+}
+
+/// A proof (plain or ZK) read in place from its bytes. Section sizes are
+/// those of the [`Flavor`] it was created for.
+#[derive(Clone, Debug)]
+pub struct ProofView<'a, D: ByteBuf> {
+    bytes: &'a D,
+    format: ProofFormat,
+    zk: bool,
+    log_n: usize,
+    witnesses: usize,
+    univariate_len: usize,
+    entities: usize,
+}
+
+impl<'a, D: ByteBuf> ProofView<'a, D> {
+    /// Check the length and every field of an `F` proof for a circuit of
+    /// size 2^`log_n`: scalars below r, coordinates below q and points on the
+    /// curve (or at infinity). Padding fold commitments need only canonical
+    /// coordinates.
+    pub fn new<F: Flavor>(
+        bytes: &'a D,
+        format: ProofFormat,
+        log_n: usize,
+    ) -> Result<Self, ParseError> {
+        if log_n == 0
+            || log_n > CONST_PROOF_SIZE_LOG_N
+            || bytes.len() as usize != format.proof_bytes::<F>(log_n)
+        {
+            return Err(ParseError::WrongLength);
+        }
+        let view = Self {
+            bytes,
+            format,
+            zk: F::ZK,
+            log_n,
+            witnesses: F::WITNESS.len(),
+            univariate_len: F::BATCHED_RELATION_PARTIAL_LENGTH,
+            entities: F::NUMBER_OF_ENTITIES,
+        };
+        view.check()?;
+        Ok(view)
+    }
+
+    pub fn format(&self) -> ProofFormat {
+        self.format
+    }
+
+    pub fn is_zk(&self) -> bool {
+        self.zk
+    }
+
+    pub fn log_n(&self) -> usize {
+        self.log_n
+    }
+
+    /// Sumcheck / Gemini rounds in the serialization, padding included.
+    pub fn rounds(&self) -> usize {
+        self.format.rounds(self.log_n)
+    }
+
+    pub fn pairing_point_object(&self) -> [Fr; PAIRING_POINTS_SIZE] {
+        self.frs_at(0)
+    }
+
+    pub fn witness(&self, w: WitnessCommitment) -> G1Point {
+        self.point_at(self.witness_at(w as usize))
+    }
+
+    /// Libra concatenation, grand sum and quotient commitments (ZK only).
+    pub fn libra_commitments(&self) -> [G1Point; LIBRA_COMMITMENTS] {
+        debug_assert!(self.zk);
+        let p = self.format.point_words();
+        let rest = self.evaluations_at() + self.entities + 1;
+        [
+            self.point_at(self.witness_at(self.witnesses)),
+            self.point_at(rest),
+            self.point_at(rest + p),
+        ]
+    }
+
+    /// Claimed Libra sum (ZK only).
+    pub fn libra_sum(&self) -> Fr {
+        debug_assert!(self.zk);
+        self.fr_at(self.univariates_at() - 1)
+    }
+
+    /// Coefficients of the round-`round` univariate: `N` is the flavor's
+    /// `BATCHED_RELATION_PARTIAL_LENGTH`.
+    pub fn sumcheck_univariate<const N: usize>(&self, round: usize) -> [Fr; N] {
+        let mut out = [Fr::zero(); N];
+        self.read_univariate(round, &mut out);
+        out
+    }
+
+    /// The flavor's `NUMBER_OF_ENTITIES` evaluations, zero-padded.
+    pub fn sumcheck_evaluations(&self) -> [Fr; NUMBER_OF_ENTITIES] {
+        let mut out = [Fr::zero(); NUMBER_OF_ENTITIES];
+        for (i, f) in out.iter_mut().take(self.entities).enumerate() {
+            *f = self.fr_at(self.evaluations_at() + i);
+        }
+        out
+    }
+
+    /// Libra evaluation (ZK only).
+    pub fn libra_evaluation(&self) -> Fr {
+        debug_assert!(self.zk);
+        self.fr_at(self.evaluations_at() + self.entities)
+    }
+
+    /// Gemini masking polynomial commitment (ZK only).
+    pub fn gemini_masking_poly(&self) -> G1Point {
+        debug_assert!(self.zk);
+        self.point_at(self.fold_comms_at() - 1 - self.format.point_words())
+    }
+
+    /// Gemini masking polynomial evaluation (ZK only).
+    pub fn gemini_masking_eval(&self) -> Fr {
+        debug_assert!(self.zk);
+        self.fr_at(self.fold_comms_at() - 1)
+    }
+
+    /// Fold commitment `i`, for `i < log_n - 1`.
+    pub fn gemini_fold_comm(&self, i: usize) -> G1Point {
+        debug_assert!(i + 1 < self.log_n);
+        self.point_at(self.fold_comms_at() + i * self.format.point_words())
+    }
+
+    /// Gemini evaluations of the first `log_n` rounds; the rest are zero.
+    pub fn gemini_a_evaluations(&self) -> [Fr; CONST_PROOF_SIZE_LOG_N] {
+        let mut out = [Fr::zero(); CONST_PROOF_SIZE_LOG_N];
+        for (i, a) in out.iter_mut().take(self.log_n).enumerate() {
+            *a = self.fr_at(self.a_evaluations_at() + i);
+        }
+        out
+    }
+
+    /// Libra polynomial evaluations (ZK only).
+    pub fn libra_poly_evals(&self) -> [Fr; LIBRA_EVALUATIONS] {
+        debug_assert!(self.zk);
+        self.frs_at(self.a_evaluations_at() + self.rounds())
+    }
+
+    /// Decode the whole plain proof, padding rounds included.
+    pub fn to_proof(&self) -> Proof {
+        debug_assert!(!self.zk);
+        Proof {
+            format: self.format,
+            pairing_point_object: self.pairing_point_object(),
+            w1: self.witness(WitnessCommitment::W1),
+            w2: self.witness(WitnessCommitment::W2),
+            w3: self.witness(WitnessCommitment::W3),
+            w4: self.witness(WitnessCommitment::W4),
+            lookup_read_counts: self.witness(WitnessCommitment::LookupReadCounts),
+            lookup_read_tags: self.witness(WitnessCommitment::LookupReadTags),
+            lookup_inverses: self.witness(WitnessCommitment::LookupInverses),
+            z_perm: self.witness(WitnessCommitment::ZPerm),
+            sumcheck_univariates: self.all_univariates(),
+            sumcheck_evaluations: self.sumcheck_evaluations(),
+            gemini_fold_comms: self.all_fold_comms(),
+            gemini_a_evaluations: self.all_a_evaluations(),
+            shplonk_q: self.shplonk_q(),
+            kzg_quotient: self.kzg_quotient(),
+        }
+    }
+
+    /// Decode the whole ZK proof, padding rounds included.
+    pub fn to_zk_proof(&self) -> ZkProof {
+        debug_assert!(self.zk);
+        ZkProof {
+            format: self.format,
+            pairing_point_object: self.pairing_point_object(),
+            w1: self.witness(WitnessCommitment::W1),
+            w2: self.witness(WitnessCommitment::W2),
+            w3: self.witness(WitnessCommitment::W3),
+            w4: self.witness(WitnessCommitment::W4),
+            lookup_read_counts: self.witness(WitnessCommitment::LookupReadCounts),
+            lookup_read_tags: self.witness(WitnessCommitment::LookupReadTags),
+            lookup_inverses: self.witness(WitnessCommitment::LookupInverses),
+            z_perm: self.witness(WitnessCommitment::ZPerm),
+            libra_commitments: self.libra_commitments(),
+            libra_sum: self.libra_sum(),
+            sumcheck_univariates: self.all_univariates(),
+            sumcheck_evaluations: self.sumcheck_evaluations(),
+            libra_evaluation: self.libra_evaluation(),
+            gemini_masking_poly: self.gemini_masking_poly(),
+            gemini_masking_eval: self.gemini_masking_eval(),
+            gemini_fold_comms: self.all_fold_comms(),
+            gemini_a_evaluations: self.all_a_evaluations(),
+            libra_poly_evals: self.libra_poly_evals(),
+            shplonk_q: self.shplonk_q(),
+            kzg_quotient: self.kzg_quotient(),
+        }
+    }
+
+    pub fn shplonk_q(&self) -> G1Point {
+        self.point_at(self.shplonk_q_at())
+    }
+
+    pub fn kzg_quotient(&self) -> G1Point {
+        self.point_at(self.shplonk_q_at() + self.format.point_words())
+    }
+
+    /// Round-`round` univariate coefficients into `out`.
+    pub(crate) fn read_univariate(&self, round: usize, out: &mut [Fr]) {
+        debug_assert_eq!(out.len(), self.univariate_len());
+        debug_assert!(round < self.log_n);
+        let start = self.univariates_at() + round * out.len();
+        for (i, f) in out.iter_mut().enumerate() {
+            *f = self.fr_at(start + i);
+        }
+    }
+
+    /// Raw words `start..start + words`, for hashing into the transcript.
+    pub(crate) fn raw(&self, start: usize, words: usize) -> D {
+        self.bytes
+            .range((start * 32) as u32, ((start + words) * 32) as u32)
+    }
+
+    // Word offsets of each section, in serialization order.
+
+    pub(crate) fn witness_at(&self, i: usize) -> usize {
+        PAIRING_POINTS_SIZE + i * self.format.point_words()
+    }
+
+    pub(crate) fn witnesses(&self) -> usize {
+        self.witnesses
+    }
+
+    pub(crate) fn univariate_len(&self) -> usize {
+        self.univariate_len
+    }
+
+    pub(crate) fn univariates_at(&self) -> usize {
+        let witness_end = self.witness_at(self.witnesses);
+        if self.zk {
+            // libra concatenation commitment + libra sum
+            witness_end + self.format.point_words() + 1
+        } else {
+            witness_end
+        }
+    }
+
+    pub(crate) fn evaluations_at(&self) -> usize {
+        self.univariates_at() + self.rounds() * self.univariate_len()
+    }
+
+    pub(crate) fn fold_comms_at(&self) -> usize {
+        let evaluations_end = self.evaluations_at() + self.entities;
+        if self.zk {
+            // libra evaluation, grand sum + quotient, masking poly + eval
+            evaluations_end + 1 + 3 * self.format.point_words() + 1
+        } else {
+            evaluations_end
+        }
+    }
+
+    pub(crate) fn a_evaluations_at(&self) -> usize {
+        self.fold_comms_at() + (self.rounds() - 1) * self.format.point_words()
+    }
+
+    pub(crate) fn shplonk_q_at(&self) -> usize {
+        let extra = if self.zk { LIBRA_EVALUATIONS } else { 0 };
+        self.a_evaluations_at() + self.rounds() + extra
+    }
+
+    fn word(&self, w: usize) -> [u8; 32] {
+        let mut out = [0u8; 32];
+        self.bytes.read_into((w * 32) as u32, &mut out);
+        out
+    }
+
+    fn fr_at(&self, w: usize) -> Fr {
+        Fr::from_bytes(&self.word(w))
+    }
+
+    fn frs_at<const N: usize>(&self, w: usize) -> [Fr; N] {
+        let mut out = [Fr::zero(); N];
+        for (i, f) in out.iter_mut().enumerate() {
+            *f = self.fr_at(w + i);
+        }
+        out
+    }
+
+    fn point_at(&self, w: usize) -> G1Point {
+        match self.format {
+            ProofFormat::V0_87 => G1Point {
+                x: join_limbs(&self.word(w), &self.word(w + 1)),
+                y: join_limbs(&self.word(w + 2), &self.word(w + 3)),
+            },
+        }
+    }
+
+    fn all_univariates<const N: usize>(&self) -> [[Fr; N]; CONST_PROOF_SIZE_LOG_N] {
+        let mut out = [[Fr::zero(); N]; CONST_PROOF_SIZE_LOG_N];
+        for (r, row) in out.iter_mut().take(self.rounds()).enumerate() {
+            *row = self.frs_at(self.univariates_at() + r * N);
+        }
+        out
+    }
+
+    fn all_fold_comms(&self) -> [G1Point; CONST_PROOF_SIZE_LOG_N - 1] {
+        let mut out = [G1Point::infinity(); CONST_PROOF_SIZE_LOG_N - 1];
+        for (i, c) in out.iter_mut().take(self.rounds() - 1).enumerate() {
+            *c = self.point_at(self.fold_comms_at() + i * self.format.point_words());
+        }
+        out
+    }
+
+    fn all_a_evaluations(&self) -> [Fr; CONST_PROOF_SIZE_LOG_N] {
+        let mut out = [Fr::zero(); CONST_PROOF_SIZE_LOG_N];
+        for (i, a) in out.iter_mut().take(self.rounds()).enumerate() {
+            *a = self.fr_at(self.a_evaluations_at() + i);
+        }
+        out
+    }
+
+    fn check_frs(&self, w: usize, n: usize) -> Result<(), ParseError> {
+        for i in w..w + n {
+            if self.word(i) >= MODULUS_BE {
+                return Err(ParseError::NonCanonicalScalar);
+            }
+        }
+        Ok(())
+    }
+
+    /// Coordinates of the point at word `w`, rejecting limbs outside their
+    /// window and values ≥ q.
+    fn coords_at(&self, w: usize) -> Result<([u8; 32], [u8; 32]), ParseError> {
+        let (x, y) = match self.format {
+            ProofFormat::V0_87 => (
+                combine_limbs(&self.word(w), &self.word(w + 1))?,
+                combine_limbs(&self.word(w + 2), &self.word(w + 3))?,
+            ),
+        };
+        if x >= FQ_MODULUS_BE || y >= FQ_MODULUS_BE {
+            return Err(ParseError::NonCanonicalCoordinate);
+        }
+        Ok((x, y))
+    }
+
+    fn check_points(&self, w: usize, n: usize) -> Result<(), ParseError> {
+        let p = self.format.point_words();
+        for i in (w..w + n * p).step_by(p) {
+            let (x, y) = self.coords_at(i)?;
+            check_g1_point(x, y)?;
+        }
+        Ok(())
+    }
+
+    /// Walk the layout in order, padding rounds included.
+    fn check(&self) -> Result<(), ParseError> {
+        let p = self.format.point_words();
+        let rounds = self.rounds();
+        self.check_frs(0, PAIRING_POINTS_SIZE)?;
+        pairing_points_to_g1(&self.pairing_point_object())?;
+        self.check_points(self.witness_at(0), self.witnesses)?;
+        if self.zk {
+            self.check_points(self.witness_at(self.witnesses), 1)?;
+            self.check_frs(self.univariates_at() - 1, 1)?;
+        }
+        self.check_frs(self.univariates_at(), rounds * self.univariate_len())?;
+        self.check_frs(self.evaluations_at(), self.entities)?;
+        if self.zk {
+            let rest = self.evaluations_at() + self.entities;
+            self.check_frs(rest, 1)?;
+            self.check_points(rest + 1, 3)?;
+            self.check_frs(rest + 1 + 3 * p, 1)?;
+        }
+        self.check_points(self.fold_comms_at(), self.log_n - 1)?;
+        for i in self.log_n - 1..rounds - 1 {
+            self.coords_at(self.fold_comms_at() + i * p)?;
+        }
+        self.check_frs(self.a_evaluations_at(), rounds)?;
+        if self.zk {
+            self.check_frs(self.a_evaluations_at() + rounds, LIBRA_EVALUATIONS)?;
+        }
+        self.check_points(self.shplonk_q_at(), 2)
+    }
+}
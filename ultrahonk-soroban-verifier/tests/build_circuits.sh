@@ -43,6 +43,9 @@ install_bb() {
 install_nargo
 install_bb
 
+GRUMPKIN_SRS_POINTS=65536
+GRUMPKIN_CRS="$HOME/.bb-crs/grumpkin_g1.flat.dat"
+
 # ─── build every circuit ───
 for dir in circuits/* ; do
   [ -d "$dir" ] || continue
@@ -62,6 +65,34 @@ for dir in circuits/* ; do
   bb write_vk -b "$json" -o target \
     --scheme ultra_honk --oracle_hash keccak --output_format bytes_and_fields
 
+  # ZK flavor (UltraKeccakZKFlavor) artifacts
+  mkdir -p target/zk
+  bb prove -b "$json" -w "$gz" -o target/zk \
+    --scheme ultra_honk --oracle_hash keccak --zk --output_format bytes_and_fields
+
+  bb write_vk -b "$json" -o target/zk \
+    --scheme ultra_honk --oracle_hash keccak --zk --output_format bytes_and_fields
+
+  # Poseidon2 oracle-hash (UltraFlavor) artifacts
+  mkdir -p target/poseidon2
+  bb prove -b "$json" -w "$gz" -o target/poseidon2 \
+    --scheme ultra_honk --oracle_hash poseidon2 --output_format bytes_and_fields
+
+  bb write_vk -b "$json" -o target/poseidon2 \
+    --scheme ultra_honk --oracle_hash poseidon2 --output_format bytes_and_fields
+
+  # UltraRollupHonk (IPA accumulation) artifacts
+  mkdir -p target/rollup
+  bb prove -b "$json" -w "$gz" -o target/rollup \
+    --scheme ultra_honk --oracle_hash poseidon2 --ipa_accumulation --output_format bytes_and_fields
+
+  bb write_vk -b "$json" -o target/rollup \
+    --scheme ultra_honk --oracle_hash poseidon2 --ipa_accumulation --output_format bytes_and_fields
+
+  # Grumpkin SRS for the IPA opening: the first 2^16 points of the CRS bb
+  # fetched for --ipa_accumulation, 64-byte big-endian (x, y) each
+  head -c $((GRUMPKIN_SRS_POINTS * 64)) "$GRUMPKIN_CRS" > target/rollup/grumpkin_srs
+
   bb write_solidity_verifier -s ultra_honk -k target/vk -o target/Verifier.sol
 
   popd >/dev/null
@@ -1,8 +1,38 @@
+use ark_bn254::Fq;
 use soroban_sdk::{testutils::Ledger, Bytes, Env};
 use std::{fs, path::Path};
-use ultrahonk_soroban_verifier::UltraHonkVerifier;
+use ultrahonk_soroban_verifier::{
+    backend::ByteBuf,
+    compact::{compact_proof, compact_proof_bytes, expand_compact_proof},
+    ec::{
+        g1_msm, helpers::to_affine, lhs_g2_affine, merge_msm_terms, pairing_check,
+        pairing_points_to_g1, rhs_g2_affine, MsmBackend,
+    },
+    field::Fr,
+    flavor::{Flavor, UltraFlavor, UltraZkFlavor},
+    format::{ProofFormat, VkFormat},
+    grumpkin::{msm, GrumpkinPoint},
+    ipa::load_grumpkin_srs,
+    public_inputs::PublicInputs,
+    types::{
+        G1Point, Wire, BATCHED_RELATION_PARTIAL_LENGTH, CONST_PROOF_SIZE_LOG_N, NUMBER_OF_ENTITIES,
+        PAIRING_POINTS_SIZE,
+    },
+    utils::{
+        load_proof, load_transcript, load_vk_from_bytes, load_zk_proof, transcript_to_bytes,
+        TRANSCRIPT_FIELDS,
+    },
+    verifier::VerifyError,
+    view::{ProofView, WitnessCommitment},
+    OracleHash, ParseError, UltraHonkVerifier, VkError, PROOF_BYTES, ROLLUP_PROOF_BYTES,
+    ZK_PROOF_BYTES,
+};
 
 fn run(dir: &str) -> Result<(), String> {
+    run_with(dir, OracleHash::Keccak)
+}
+
+fn run_with(dir: &str, oracle_hash: OracleHash) -> Result<(), String> {
     let path = Path::new(dir);
     let env = Env::default();
     env.ledger().set_protocol_version(25);
@@ -14,7 +44,9 @@ fn run(dir: &str) -> Result<(), String> {
     // Use binary VK
     let vk_bytes = fs::read(path.join("vk")).map_err(|e| e.to_string())?;
     let vk = Bytes::from_slice(&env, &vk_bytes);
-    let verifier = UltraHonkVerifier::new(&env, &vk).map_err(|e| format!("{e:?}"))?;
+    let verifier = UltraHonkVerifier::new(&env, &vk)
+        .map_err(|e| format!("{e:?}"))?
+        .with_oracle_hash(oracle_hash);
 
     // Public inputs bytes
     let public_inputs = fs::read(path.join("public_inputs")).map_err(|e| e.to_string())?;
@@ -34,3 +66,1247 @@ fn simple_circuit_proof_verifies() -> Result<(), String> {
 fn fib_chain_proof_verifies() -> Result<(), String> {
     run("circuits/fib_chain/target")
 }
+
+#[test]
+fn simple_circuit_zk_proof_verifies() -> Result<(), String> {
+    run("circuits/simple_circuit/target/zk")
+}
+
+#[test]
+fn fib_chain_zk_proof_verifies() -> Result<(), String> {
+    run("circuits/fib_chain/target/zk")
+}
+
+#[test]
+fn simple_circuit_poseidon2_proof_verifies() -> Result<(), String> {
+    run_with(
+        "circuits/simple_circuit/target/poseidon2",
+        OracleHash::Poseidon2,
+    )
+}
+
+#[test]
+fn fib_chain_poseidon2_proof_verifies() -> Result<(), String> {
+    run_with("circuits/fib_chain/target/poseidon2", OracleHash::Poseidon2)
+}
+
+#[test]
+fn v0_87_layout_is_detected() -> Result<(), String> {
+    let path = Path::new("circuits/simple_circuit/target");
+    let env = Env::default();
+
+    let vk_bytes = fs::read(path.join("vk")).map_err(|e| e.to_string())?;
+    let vk_bytes = Bytes::from_slice(&env, &vk_bytes);
+    assert_eq!(VkFormat::detect(&vk_bytes), Some(VkFormat::V0_87));
+    assert_eq!(VkFormat::detect(&vk_bytes.slice(32..)), None);
+    let vk = load_vk_from_bytes(&vk_bytes).map_err(|e| format!("{e:?}"))?;
+    let log_n = vk.log_circuit_size as usize;
+
+    assert_eq!(
+        ProofFormat::V0_87.proof_bytes::<UltraFlavor>(log_n),
+        PROOF_BYTES
+    );
+    assert_eq!(
+        ProofFormat::V0_87.proof_bytes::<UltraZkFlavor>(log_n),
+        ZK_PROOF_BYTES
+    );
+    assert_eq!(
+        ProofFormat::detect(PROOF_BYTES, log_n),
+        Some((ProofFormat::V0_87, false))
+    );
+    assert_eq!(
+        ProofFormat::detect(ZK_PROOF_BYTES, log_n),
+        Some((ProofFormat::V0_87, true))
+    );
+    assert_eq!(ProofFormat::detect(PROOF_BYTES - 32, log_n), None);
+    assert!(!ProofFormat::is_known_len(compact_proof_bytes(log_n)));
+    Ok(())
+}
+
+#[test]
+fn vk_and_proof_round_trip_through_to_bytes() -> Result<(), String> {
+    let env = Env::default();
+    for dir in [
+        "circuits/simple_circuit/target",
+        "circuits/fib_chain/target",
+    ] {
+        let path = Path::new(dir);
+        let vk_bytes = fs::read(path.join("vk")).map_err(|e| e.to_string())?;
+        let vk = load_vk_from_bytes(&Bytes::from_slice(&env, &vk_bytes))
+            .map_err(|e| format!("{e:?}"))?;
+        assert_eq!(vk.to_bytes(), vk_bytes, "{dir}");
+        let again = load_vk_from_bytes(&Bytes::from_slice(&env, &vk.to_bytes()))
+            .map_err(|e| format!("{e:?}"))?;
+        assert_eq!(again.to_bytes(), vk_bytes);
+        let log_n = vk.log_circuit_size as usize;
+
+        let proof_bytes = fs::read(path.join("proof")).map_err(|e| e.to_string())?;
+        let proof =
+            load_proof(&Bytes::from_slice(&env, &proof_bytes)).map_err(|e| format!("{e:?}"))?;
+        assert_eq!(proof.to_bytes(log_n), proof_bytes, "{dir}");
+    }
+    Ok(())
+}
+
+#[test]
+fn compact_proof_expands_to_bb_bytes() -> Result<(), String> {
+    let env = Env::default();
+    env.ledger().set_protocol_version(25);
+    for dir in [
+        "circuits/simple_circuit/target",
+        "circuits/fib_chain/target",
+    ] {
+        let path = Path::new(dir);
+        let vk_bytes = fs::read(path.join("vk")).map_err(|e| e.to_string())?;
+        let verifier = UltraHonkVerifier::new(&env, &Bytes::from_slice(&env, &vk_bytes))
+            .map_err(|e| format!("{e:?}"))?;
+        let log_n = verifier.get_vk().log_circuit_size as usize;
+
+        let proof_bytes = fs::read(path.join("proof")).map_err(|e| e.to_string())?;
+        let compact = compact_proof(&proof_bytes, log_n).map_err(|e| format!("{e:?}"))?;
+        assert_eq!(compact.len(), compact_proof_bytes(log_n));
+        assert!(compact.len() < proof_bytes.len());
+        assert_eq!(
+            expand_compact_proof(&compact, log_n).map_err(|e| format!("{e:?}"))?,
+            proof_bytes
+        );
+
+        let public_inputs = fs::read(path.join("public_inputs")).map_err(|e| e.to_string())?;
+        let public_inputs = Bytes::from_slice(&env, &public_inputs);
+        let compact = Bytes::from_slice(&env, &compact);
+        verifier
+            .verify_compact(&compact, &public_inputs)
+            .map_err(|e| format!("{e:?}"))?;
+        assert_eq!(
+            verifier.verify_compact(&compact.slice(32..), &public_inputs),
+            Err(VerifyError::Parse(ParseError::WrongLength))
+        );
+    }
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn json_artifacts_load_like_binary() -> Result<(), String> {
+    use ultrahonk_soroban_verifier::json::{
+        load_proof_from_json, load_vk_from_json, proof_bytes_from_json, public_inputs_from_json,
+    };
+
+    let path = Path::new("circuits/simple_circuit/target");
+    let env = Env::default();
+    env.ledger().set_protocol_version(25);
+    let read = |name: &str| fs::read(path.join(name)).map_err(|e| e.to_string());
+    let read_json = |name: &str| fs::read_to_string(path.join(name)).map_err(|e| e.to_string());
+
+    let vk_bytes = read("vk")?;
+    let vk =
+        load_vk_from_bytes(&Bytes::from_slice(&env, &vk_bytes)).map_err(|e| format!("{e:?}"))?;
+    let json_vk = load_vk_from_json(&read_json("vk_fields.json")?).map_err(|e| format!("{e:?}"))?;
+    assert_eq!(json_vk.circuit_size, vk.circuit_size);
+    assert_eq!(json_vk.log_circuit_size, vk.log_circuit_size);
+    assert_eq!(json_vk.public_inputs_size, vk.public_inputs_size);
+    assert_eq!(json_vk.pub_inputs_offset, vk.pub_inputs_offset);
+    for &w in UltraFlavor::PRECOMPUTED {
+        assert_eq!(json_vk.commitment(w), vk.commitment(w), "{w:?}");
+    }
+    assert_eq!(json_vk.to_bytes(), vk_bytes);
+
+    let proof_bytes = read("proof")?;
+    let proof_json = read_json("proof_fields.json")?;
+    let proof = load_proof(&Bytes::from_slice(&env, &proof_bytes)).map_err(|e| format!("{e:?}"))?;
+    let json_proof = load_proof_from_json(&proof_json).map_err(|e| format!("{e:?}"))?;
+    assert_eq!(json_proof.pairing_point_object, proof.pairing_point_object);
+    assert_eq!(json_proof.w1, proof.w1);
+    assert_eq!(json_proof.z_perm, proof.z_perm);
+    assert_eq!(json_proof.sumcheck_univariates, proof.sumcheck_univariates);
+    assert_eq!(json_proof.sumcheck_evaluations, proof.sumcheck_evaluations);
+    assert_eq!(json_proof.gemini_fold_comms, proof.gemini_fold_comms);
+    assert_eq!(json_proof.gemini_a_evaluations, proof.gemini_a_evaluations);
+    assert_eq!(json_proof.shplonk_q, proof.shplonk_q);
+    assert_eq!(json_proof.kzg_quotient, proof.kzg_quotient);
+
+    let proof = proof_bytes_from_json(&env, &proof_json).map_err(|e| format!("{e:?}"))?;
+    assert_eq!(proof, Bytes::from_slice(&env, &proof_bytes));
+    let public_inputs = public_inputs_from_json(&env, &read_json("public_inputs_fields.json")?)
+        .map_err(|e| format!("{e:?}"))?;
+    assert_eq!(
+        public_inputs,
+        Bytes::from_slice(&env, &read("public_inputs")?)
+    );
+
+    assert_eq!(
+        load_vk_from_json("[\"0x1\", \"0x2\"]").err(),
+        Some(ParseError::WrongLength)
+    );
+    assert_eq!(
+        load_proof_from_json("[\"0xzz\"]").err(),
+        Some(ParseError::NonCanonicalScalar)
+    );
+
+    UltraHonkVerifier::new(&env, &Bytes::from_slice(&env, &vk_bytes))
+        .map_err(|e| format!("{e:?}"))?
+        .verify(&proof, &public_inputs)
+        .map_err(|e| format!("{e:?}"))
+}
+
+#[test]
+fn public_inputs_are_read_by_index() {
+    let env = Env::default();
+    let mut words = Vec::new();
+    words.extend_from_slice(&Fr::from_u64(89).to_bytes());
+    words.extend_from_slice(&Fr::from_u64(u64::MAX).to_bytes());
+    words.extend_from_slice(&Fr::one().to_bytes());
+    let mut word = Fr::from_u64(7).to_bytes();
+    add_scalar_modulus(&mut word);
+    words.extend_from_slice(&word);
+    let bytes = Bytes::from_slice(&env, &words);
+
+    let inputs = PublicInputs::new(&bytes).unwrap();
+    assert_eq!(inputs.len(), 4);
+    assert_eq!(inputs.fr(0), Ok(Fr::from_u64(89)));
+    assert_eq!(inputs.field(0), Ok(Fr::from_u64(89).to_bytes()));
+    assert_eq!(inputs.u64(1), Ok(u64::MAX));
+    assert_eq!(inputs.bool(2), Ok(true));
+    assert_eq!(inputs.bool(0), Err(ParseError::NonCanonicalScalar));
+    assert_eq!(inputs.field(3), Err(ParseError::NonCanonicalScalar));
+    assert_eq!(inputs.u64(3), Err(ParseError::NonCanonicalScalar));
+    assert_eq!(inputs.field(4), Err(ParseError::WrongLength));
+    assert_eq!(
+        PublicInputs::new(&bytes.slice(1..)).err(),
+        Some(ParseError::WrongLength)
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn noir_abi_encodes_public_inputs() {
+    use ultrahonk_soroban_verifier::abi::{AbiError, AbiType, AbiValue, PublicAbi};
+
+    let abi = PublicAbi::from_json(
+        r#"{"noir_version": "1.0.0-beta.9", "abi": {
+            "parameters": [
+                {"name": "secret", "type": {"kind": "field"}, "visibility": "private"},
+                {"name": "root", "type": {"kind": "field"}, "visibility": "public"},
+                {"name": "amount", "type": {"kind": "integer", "sign": "unsigned", "width": 64}, "visibility": "public"},
+                {"name": "flags", "type": {"kind": "array", "length": 2, "type": {"kind": "boolean"}}, "visibility": "public"},
+                {"name": "note", "type": {"kind": "struct", "path": "Note", "fields": [
+                    {"name": "delta", "type": {"kind": "integer", "sign": "signed", "width": 8}},
+                    {"name": "tag", "type": {"kind": "string", "length": 2}}
+                ]}, "visibility": "public"}
+            ],
+            "return_type": {"abi_type": {"kind": "tuple", "fields": [{"kind": "field"}, {"kind": "boolean"}]}, "visibility": "public"},
+            "error_types": {}
+        }}"#,
+    )
+    .unwrap();
+    assert_eq!(abi.parameters.len(), 4);
+    assert_eq!(
+        abi.parameters[1].1,
+        AbiType::Integer {
+            signed: false,
+            width: 64
+        }
+    );
+    assert_eq!(abi.num_fields(), 9);
+    assert_eq!(abi.index_of("root"), Some(0));
+    assert_eq!(abi.index_of("note"), Some(4));
+    assert_eq!(abi.index_of("secret"), None);
+    assert_eq!(abi.return_index(), Some(7));
+
+    let inputs = vec![
+        (
+            "note".to_string(),
+            AbiValue::Struct(vec![
+                ("tag".to_string(), AbiValue::String("ok".to_string())),
+                ("delta".to_string(), AbiValue::Signed(-1)),
+            ]),
+        ),
+        ("root".to_string(), AbiValue::Field(Fr::from_u64(42))),
+        ("amount".to_string(), AbiValue::Unsigned(1000)),
+        (
+            "flags".to_string(),
+            AbiValue::Array(vec![AbiValue::Boolean(true), AbiValue::Boolean(false)]),
+        ),
+    ];
+    let ret = AbiValue::Array(vec![
+        AbiValue::Field(Fr::from_u64(3)),
+        AbiValue::Boolean(true),
+    ]);
+    let bytes = abi.encode(&inputs, Some(&ret)).unwrap();
+    let expected: Vec<u64> = vec![42, 1000, 1, 0, 0xff, b'o' as u64, b'k' as u64, 3, 1];
+    let expected: Vec<u8> = expected
+        .into_iter()
+        .flat_map(|v| Fr::from_u64(v).to_bytes())
+        .collect();
+    assert_eq!(bytes, expected);
+
+    // Decoding gives the values back in ABI order.
+    let (decoded, decoded_ret) = abi.decode(&bytes).unwrap();
+    assert_eq!(decoded[0], inputs[1]);
+    assert_eq!(decoded[1], inputs[2]);
+    assert_eq!(decoded[2], inputs[3]);
+    assert_eq!(
+        decoded[3].1,
+        AbiValue::Struct(vec![
+            ("delta".to_string(), AbiValue::Signed(-1)),
+            ("tag".to_string(), AbiValue::String("ok".to_string())),
+        ])
+    );
+    assert_eq!(decoded_ret, Some(ret.clone()));
+    assert_eq!(
+        abi.encode(&decoded, decoded_ret.as_ref()),
+        Ok(bytes.clone())
+    );
+
+    // Named inputs on-chain, by the ABI index.
+    let env = Env::default();
+    let public_inputs = Bytes::from_slice(&env, &bytes);
+    let view = PublicInputs::new(&public_inputs).unwrap();
+    assert_eq!(view.u64(abi.index_of("amount").unwrap()), Ok(1000));
+    assert_eq!(view.bool(abi.index_of("flags").unwrap()), Ok(true));
+
+    let mut wide = inputs.clone();
+    wide[2].1 = AbiValue::Unsigned(1 << 64);
+    assert_eq!(abi.encode(&wide, Some(&ret)), Err(AbiError::TypeMismatch));
+    assert_eq!(
+        abi.encode(&inputs[1..], Some(&ret)),
+        Err(AbiError::MissingValue)
+    );
+    assert_eq!(abi.encode(&inputs, None), Err(AbiError::MissingValue));
+    let mut bad = bytes.clone();
+    bad[2 * 32 + 31] = 2;
+    assert_eq!(abi.decode(&bad), Err(AbiError::TypeMismatch));
+    assert_eq!(abi.decode(&bytes[32..]), Err(AbiError::WrongLength));
+    assert_eq!(PublicAbi::from_json("[]"), Err(AbiError::BadAbi));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn noir_abi_decodes_committed_public_inputs() -> Result<(), String> {
+    use ultrahonk_soroban_verifier::abi::{AbiValue, PublicAbi};
+
+    let path = Path::new("circuits/fib_chain/target");
+    let json = fs::read_to_string(path.join("fib_chain.json")).map_err(|e| e.to_string())?;
+    let abi = PublicAbi::from_json(&json).map_err(|e| format!("{e:?}"))?;
+    let public_inputs = fs::read(path.join("public_inputs")).map_err(|e| e.to_string())?;
+
+    let (inputs, ret) = abi.decode(&public_inputs).map_err(|e| format!("{e:?}"))?;
+    assert_eq!(
+        inputs,
+        vec![("out".to_string(), AbiValue::Field(Fr::from_u64(89)))]
+    );
+    assert_eq!(ret, None);
+    assert_eq!(abi.encode(&inputs, None), Ok(public_inputs));
+    Ok(())
+}
+
+#[test]
+fn proof_view_reads_like_loader() -> Result<(), String> {
+    let path = Path::new("circuits/simple_circuit/target");
+    let env = Env::default();
+    env.ledger().set_protocol_version(25);
+
+    let vk_bytes = fs::read(path.join("vk")).map_err(|e| e.to_string())?;
+    let verifier = UltraHonkVerifier::new(&env, &Bytes::from_slice(&env, &vk_bytes))
+        .map_err(|e| format!("{e:?}"))?;
+    let log_n = verifier.get_vk().log_circuit_size as usize;
+    let public_inputs = fs::read(path.join("public_inputs")).map_err(|e| e.to_string())?;
+    let public_inputs = Bytes::from_slice(&env, &public_inputs);
+
+    let proof_bytes = fs::read(path.join("proof")).map_err(|e| e.to_string())?;
+    let bytes = Bytes::from_slice(&env, &proof_bytes);
+    let proof = load_proof(&bytes).map_err(|e| format!("{e:?}"))?;
+    let view = ProofView::new::<UltraFlavor>(&bytes, ProofFormat::V0_87, log_n)
+        .map_err(|e| format!("{e:?}"))?;
+    assert_eq!(view.pairing_point_object(), proof.pairing_point_object);
+    assert_eq!(view.witness(WitnessCommitment::W1), proof.w1);
+    assert_eq!(view.witness(WitnessCommitment::W4), proof.w4);
+    assert_eq!(view.witness(WitnessCommitment::ZPerm), proof.z_perm);
+    for round in 0..log_n {
+        assert_eq!(
+            view.sumcheck_univariate::<BATCHED_RELATION_PARTIAL_LENGTH>(round),
+            proof.sumcheck_univariates[round]
+        );
+    }
+    assert_eq!(view.sumcheck_evaluations(), proof.sumcheck_evaluations);
+    for i in 0..log_n - 1 {
+        assert_eq!(view.gemini_fold_comm(i), proof.gemini_fold_comms[i]);
+    }
+    assert_eq!(
+        view.gemini_a_evaluations()[..log_n],
+        proof.gemini_a_evaluations[..log_n]
+    );
+    assert_eq!(view.shplonk_q(), proof.shplonk_q);
+    assert_eq!(view.kzg_quotient(), proof.kzg_quotient);
+
+    let zk_path = path.join("zk");
+    let zk_bytes = fs::read(zk_path.join("proof")).map_err(|e| e.to_string())?;
+    let zk_bytes = Bytes::from_slice(&env, &zk_bytes);
+    let zk_vk = fs::read(zk_path.join("vk")).map_err(|e| e.to_string())?;
+    let zk_vk =
+        load_vk_from_bytes(&Bytes::from_slice(&env, &zk_vk)).map_err(|e| format!("{e:?}"))?;
+    let zk_log_n = zk_vk.log_circuit_size as usize;
+    let proof = load_zk_proof(&zk_bytes).map_err(|e| format!("{e:?}"))?;
+    let view = ProofView::new::<UltraZkFlavor>(&zk_bytes, ProofFormat::V0_87, zk_log_n)
+        .map_err(|e| format!("{e:?}"))?;
+    assert_eq!(view.libra_commitments(), proof.libra_commitments);
+    assert_eq!(view.libra_sum(), proof.libra_sum);
+    assert_eq!(view.libra_evaluation(), proof.libra_evaluation);
+    assert_eq!(view.gemini_masking_poly(), proof.gemini_masking_poly);
+    assert_eq!(view.gemini_masking_eval(), proof.gemini_masking_eval);
+    assert_eq!(view.libra_poly_evals(), proof.libra_poly_evals);
+    assert_eq!(view.kzg_quotient(), proof.kzg_quotient);
+
+    // Padding rounds are never decoded but must still be canonical: a
+    // coefficient plus r, or a fold commitment limb past its window, would
+    // otherwise hash like the original under Poseidon2.
+    let mut padded = proof_bytes.clone();
+    let off = PAIRING_POINTS_SIZE * 32 + 8 * 4 * 32 + log_n * BATCHED_RELATION_PARTIAL_LENGTH * 32;
+    add_scalar_modulus(&mut padded[off..off + 32]);
+    let padded = Bytes::from_slice(&env, &padded);
+    assert_eq!(
+        ProofView::new::<UltraFlavor>(&padded, ProofFormat::V0_87, log_n).err(),
+        Some(ParseError::NonCanonicalScalar)
+    );
+    assert_eq!(
+        verifier.verify(&padded, &public_inputs),
+        Err(VerifyError::Parse(ParseError::NonCanonicalScalar))
+    );
+
+    let mut padded = proof_bytes.clone();
+    // top byte of the last fold commitment's x_lo limb
+    let fold_comms = PAIRING_POINTS_SIZE
+        + 8 * 4
+        + CONST_PROOF_SIZE_LOG_N * BATCHED_RELATION_PARTIAL_LENGTH
+        + NUMBER_OF_ENTITIES;
+    padded[(fold_comms + (CONST_PROOF_SIZE_LOG_N - 2) * 4) * 32] = 1;
+    let padded = Bytes::from_slice(&env, &padded);
+    assert_eq!(
+        ProofView::new::<UltraFlavor>(&padded, ProofFormat::V0_87, log_n).err(),
+        Some(ParseError::NonCanonicalCoordinate)
+    );
+    Ok(())
+}
+
+#[test]
+fn pairing_point_object_is_a_valid_accumulator() -> Result<(), String> {
+    let path = Path::new("circuits/simple_circuit/target");
+    let env = Env::default();
+    env.ledger().set_protocol_version(25);
+
+    let proof_bytes = fs::read(path.join("proof")).map_err(|e| e.to_string())?;
+    let proof = load_proof(&Bytes::from_slice(&env, &proof_bytes)).map_err(|e| format!("{e:?}"))?;
+    let (lhs, rhs) =
+        pairing_points_to_g1(&proof.pairing_point_object).map_err(|e| format!("{e:?}"))?;
+    assert!(pairing_check(
+        &env,
+        &to_affine(&env, &lhs)?,
+        &to_affine(&env, &rhs)?
+    ));
+
+    // rhs.y's low limb off by one: still canonical, no longer on the curve
+    let vk = Bytes::from_slice(&env, &fs::read(path.join("vk")).map_err(|e| e.to_string())?);
+    let public_inputs = fs::read(path.join("public_inputs")).map_err(|e| e.to_string())?;
+    let verifier = UltraHonkVerifier::new(&env, &vk).map_err(|e| format!("{e:?}"))?;
+    let mut tweaked = proof_bytes.clone();
+    tweaked[12 * 32 + 31] ^= 1;
+    assert_eq!(
+        verifier.verify(
+            &Bytes::from_slice(&env, &tweaked),
+            &Bytes::from_slice(&env, &public_inputs)
+        ),
+        Err(VerifyError::Parse(ParseError::PointNotOnCurve))
+    );
+    Ok(())
+}
+
+#[test]
+fn off_curve_pairing_points_are_rejected() {
+    // lhs = rhs = the generator (1, 2): each coordinate is its lowest limb
+    let mut ppo = [Fr::zero(); PAIRING_POINTS_SIZE];
+    for p in [0, 8] {
+        ppo[p] = Fr::from_u64(1);
+        ppo[p + 4] = Fr::from_u64(2);
+    }
+    assert_eq!(
+        pairing_points_to_g1(&ppo),
+        Ok((G1Point::generator(), G1Point::generator()))
+    );
+
+    // rhs = (1, 3): 3² ≠ 1³ + 3
+    ppo[12] = Fr::from_u64(3);
+    assert_eq!(pairing_points_to_g1(&ppo), Err(ParseError::PointNotOnCurve));
+
+    // a limb wider than 68 bits
+    ppo[12] = Fr::from_u64(2);
+    ppo[1] = Fr::from_bytes(&[0xff; 32]);
+    assert_eq!(
+        pairing_points_to_g1(&ppo),
+        Err(ParseError::NonCanonicalCoordinate)
+    );
+}
+
+#[test]
+fn batch_verification_uses_one_pairing() -> Result<(), String> {
+    let path = Path::new("circuits/simple_circuit/target");
+    let env = Env::default();
+    env.ledger().set_protocol_version(25);
+    let proof_bytes = fs::read(path.join("proof")).map_err(|e| e.to_string())?;
+    let vk = Bytes::from_slice(&env, &fs::read(path.join("vk")).map_err(|e| e.to_string())?);
+    let public_inputs = Bytes::from_slice(
+        &env,
+        &fs::read(path.join("public_inputs")).map_err(|e| e.to_string())?,
+    );
+    let verifier = UltraHonkVerifier::new(&env, &vk).map_err(|e| format!("{e:?}"))?;
+    let good = (Bytes::from_slice(&env, &proof_bytes), public_inputs.clone());
+
+    verifier
+        .verify_batch(std::slice::from_ref(&good))
+        .map_err(|e| format!("{e:?}"))?;
+    verifier
+        .verify_batch(&[good.clone(), good.clone(), good.clone()])
+        .map_err(|e| format!("{e:?}"))?;
+    assert_eq!(
+        verifier.verify_batch(&[]),
+        Err(VerifyError::InvalidInput("empty proof batch"))
+    );
+
+    // The KZG quotient is not absorbed by the transcript: replacing it with
+    // the generator passes sum-check and only fails the pairing.
+    let mut forged = proof_bytes.clone();
+    let quotient = forged.len() - 128;
+    forged[quotient..].fill(0);
+    forged[quotient + 31] = 1;
+    forged[quotient + 95] = 2;
+    let forged = (Bytes::from_slice(&env, &forged), public_inputs);
+    assert!(matches!(
+        verifier.verify(&forged.0, &forged.1),
+        Err(VerifyError::ShplonkFailed(_))
+    ));
+    assert_eq!(
+        verifier.verify_batch(&[good.clone(), forged.clone()]),
+        Err(VerifyError::ShplonkFailed("batched pairing check failed"))
+    );
+    assert_eq!(
+        verifier.verify_batch(&[forged, good]),
+        Err(VerifyError::ShplonkFailed("batched pairing check failed"))
+    );
+    Ok(())
+}
+
+#[test]
+fn deferred_pairing_settles_like_verify() -> Result<(), String> {
+    for dir in [
+        "circuits/simple_circuit/target",
+        "circuits/simple_circuit/target/zk",
+    ] {
+        let path = Path::new(dir);
+        let env = Env::default();
+        env.ledger().set_protocol_version(25);
+        let mut proof_bytes = fs::read(path.join("proof")).map_err(|e| e.to_string())?;
+        let vk = Bytes::from_slice(&env, &fs::read(path.join("vk")).map_err(|e| e.to_string())?);
+        let public_inputs = Bytes::from_slice(
+            &env,
+            &fs::read(path.join("public_inputs")).map_err(|e| e.to_string())?,
+        );
+        let verifier = UltraHonkVerifier::new(&env, &vk).map_err(|e| format!("{e:?}"))?;
+
+        let deferred = verifier
+            .verify_deferred(&Bytes::from_slice(&env, &proof_bytes), &public_inputs)
+            .map_err(|e| format!("{e:?}"))?;
+        assert!(deferred.check(&env));
+        assert_eq!(deferred.rhs_g2, rhs_g2_affine(&env));
+        assert_eq!(deferred.lhs_g2, lhs_g2_affine(&env));
+        assert!(pairing_check(&env, &deferred.p0, &deferred.p1));
+
+        // A forged KZG quotient still yields an accumulator, which fails to settle
+        let quotient = proof_bytes.len() - 128;
+        proof_bytes[quotient..].fill(0);
+        proof_bytes[quotient + 31] = 1;
+        proof_bytes[quotient + 95] = 2;
+        let deferred = verifier
+            .verify_deferred(&Bytes::from_slice(&env, &proof_bytes), &public_inputs)
+            .map_err(|e| format!("{e:?}"))?;
+        assert!(!deferred.check(&env));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "native")]
+#[test]
+fn native_backend_matches_host() {
+    use ultrahonk_soroban_verifier::{
+        backend::{Backend, NativeBackend},
+        hash::TranscriptHasher,
+    };
+
+    let env = Env::default();
+    env.ledger().set_protocol_version(25);
+    let native = NativeBackend;
+
+    let data: Vec<u8> = (0..100u8).collect();
+    assert_eq!(
+        native.keccak256(&data),
+        env.keccak256(&Bytes::from_slice(&env, &data))
+    );
+    // No Poseidon2 natively: an error, not an abort
+    assert_eq!(native.poseidon2(&data), None);
+    assert_eq!(OracleHash::Poseidon2.hash(&native, &data), None);
+
+    let g = G1Point::generator();
+    let (g_env, g_native) = (env.g1(&g).unwrap(), native.g1(&g).unwrap());
+    let mut points = vec![g];
+    for k in 2..=6u64 {
+        let p = native.g1_mul(&g_native, &Fr::from_u64(k));
+        let p = G1Point::from_bytes(native.g1_to_bytes(&p));
+        assert_eq!(
+            p.to_bytes(),
+            env.g1_to_bytes(&env.g1_mul(&g_env, &Fr::from_u64(k)))
+        );
+        points.push(p);
+    }
+    points.push(G1Point::infinity());
+    let mut off_curve = g;
+    off_curve.y[31] = 3;
+    assert_eq!(native.g1(&off_curve), Err(ParseError::PointNotOnCurve));
+    assert_eq!(
+        g1_msm(&native, MsmBackend::Windowed, &[off_curve], &[Fr::one()]),
+        Err("point not on curve")
+    );
+    let scalars: Vec<Fr> = (0..points.len() as u64)
+        .map(|i| Fr::from_u64(i * i + 7) - Fr::from_u64(10))
+        .collect();
+    let expected = env.g1_to_bytes(&g1_msm(&env, MsmBackend::PerTerm, &points, &scalars).unwrap());
+    for msm in [MsmBackend::PerTerm, MsmBackend::Windowed] {
+        let got = g1_msm(&native, msm, &points, &scalars).expect("msm");
+        assert_eq!(native.g1_to_bytes(&got), expected);
+    }
+
+    // e(G, Q)·e(-G, Q) == 1 against each fixed G2 point, on both backends
+    for (q_env, q_native) in [
+        (rhs_g2_affine(&env), rhs_g2_affine(&native)),
+        (lhs_g2_affine(&env), lhs_g2_affine(&native)),
+    ] {
+        let (p, n) = (g_env.clone(), env.g1_neg(&g_env));
+        assert!(env.pairing_check(&[p.clone(), n], &[q_env.clone(), q_env.clone()]));
+        assert!(!env.pairing_check(&[p.clone(), p], &[q_env.clone(), q_env]));
+        let (p, n) = (g_native, native.g1_neg(&g_native));
+        assert!(native.pairing_check(&[p, n], &[q_native, q_native]));
+        assert!(!native.pairing_check(&[p, p], &[q_native, q_native]));
+    }
+}
+
+#[cfg(feature = "native")]
+#[test]
+fn native_backend_verifies_like_host() -> Result<(), String> {
+    use ultrahonk_soroban_verifier::backend::NativeBackend;
+
+    for dir in [
+        "circuits/simple_circuit/target",
+        "circuits/fib_chain/target",
+        "circuits/simple_circuit/target/zk",
+        "circuits/fib_chain/target/zk",
+    ] {
+        let path = Path::new(dir);
+        let env = Env::default();
+        env.ledger().set_protocol_version(25);
+        let mut proof = fs::read(path.join("proof")).map_err(|e| e.to_string())?;
+        let vk = fs::read(path.join("vk")).map_err(|e| e.to_string())?;
+        let public_inputs = fs::read(path.join("public_inputs")).map_err(|e| e.to_string())?;
+
+        let host = UltraHonkVerifier::new(&env, &Bytes::from_slice(&env, &vk))
+            .map_err(|e| format!("{e:?}"))?;
+        let native = UltraHonkVerifier::new(&NativeBackend, &vk).map_err(|e| format!("{e:?}"))?;
+        let host_pis = Bytes::from_slice(&env, &public_inputs);
+        assert_eq!(
+            host.verify(&Bytes::from_slice(&env, &proof), &host_pis),
+            Ok(())
+        );
+        assert_eq!(native.verify(&proof, &public_inputs), Ok(()));
+
+        // A forged KZG quotient fails the pairing on both
+        let quotient = proof.len() - 128;
+        proof[quotient..].fill(0);
+        proof[quotient + 31] = 1;
+        proof[quotient + 95] = 2;
+        let host_result = host.verify(&Bytes::from_slice(&env, &proof), &host_pis);
+        assert!(matches!(host_result, Err(VerifyError::ShplonkFailed(_))));
+        assert_eq!(native.verify(&proof, &public_inputs), host_result);
+    }
+
+    // No Poseidon2 natively
+    let vk = fs::read("circuits/simple_circuit/target/poseidon2/vk").map_err(|e| e.to_string())?;
+    let native = UltraHonkVerifier::new(&NativeBackend, &vk)
+        .map_err(|e| format!("{e:?}"))?
+        .with_oracle_hash(OracleHash::Poseidon2);
+    let proof =
+        fs::read("circuits/simple_circuit/target/poseidon2/proof").map_err(|e| e.to_string())?;
+    let pis = fs::read("circuits/simple_circuit/target/poseidon2/public_inputs")
+        .map_err(|e| e.to_string())?;
+    assert!(matches!(
+        native.verify(&proof, &pis),
+        Err(VerifyError::InvalidInput(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn merged_msm_matches_full_layout() {
+    let env = Env::default();
+    env.ledger().set_protocol_version(25);
+    let bn = env.crypto().bn254();
+    let g = to_affine(&env, &G1Point::generator()).expect("generator");
+    let points: Vec<G1Point> = (1..=8u64)
+        .map(|k| {
+            let s = soroban_sdk::crypto::bn254::Fr::from_bytes(soroban_sdk::BytesN::from_array(
+                &env,
+                &Fr::from_u64(k).to_bytes(),
+            ));
+            G1Point::from_bytes(bn.g1_mul(&g, &s).to_array())
+        })
+        .collect();
+
+    // Shplemini-like layout: entities, shifted duplicates of the first five,
+    // zero-scalar padding and a point at infinity
+    let mut coms = points.clone();
+    coms.extend_from_slice(&points[..5]);
+    coms.extend_from_slice(&[points[6]; 3]);
+    coms.push(G1Point::infinity());
+    let mut x = Fr::from_u64(3);
+    let mut scalars: Vec<Fr> = (0..coms.len() as u64)
+        .map(|i| {
+            x = x * x + Fr::from_u64(i);
+            x
+        })
+        .collect();
+    let padding = points.len() + 5;
+    for s in &mut scalars[padding..] {
+        *s = Fr::zero();
+    }
+    // a duplicate that cancels its first occurrence
+    scalars[points.len() + 4] = -scalars[4];
+
+    let full = g1_msm(&env, MsmBackend::PerTerm, &coms, &scalars).expect("msm");
+    let n = merge_msm_terms(&mut coms, &mut scalars).expect("merge");
+    assert_eq!(n, points.len());
+    assert_eq!(&coms[..n], &points[..]);
+    assert!(scalars[4].is_zero());
+    for backend in [MsmBackend::PerTerm, MsmBackend::Windowed] {
+        let merged = g1_msm(&env, backend, &coms[..n], &scalars[..n]).expect("msm");
+        assert_eq!(merged.to_array(), full.to_array());
+    }
+}
+
+/// BN254 scalar field modulus r (big-endian).
+const R_BE: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xf0, 0x00, 0x00, 0x01,
+];
+
+/// Add r to a 32-byte big-endian word: same field element, different bytes.
+fn add_scalar_modulus(word: &mut [u8]) {
+    let mut carry = 0u16;
+    for i in (0..32).rev() {
+        let sum = word[i] as u16 + R_BE[i] as u16 + carry;
+        word[i] = sum as u8;
+        carry = sum >> 8;
+    }
+    assert_eq!(carry, 0, "word + r overflows 256 bits");
+}
+
+#[test]
+fn canonical_field_decoding() {
+    let mut r_minus_one = R_BE;
+    r_minus_one[31] -= 1;
+    assert_eq!(Fr::from_bytes_canonical(&r_minus_one), Some(-Fr::one()));
+    assert_eq!(Fr::from_bytes_canonical(&R_BE), None);
+    assert_eq!(Fr::from_bytes_canonical(&[0xff; 32]), None);
+    // the reducing constructor still folds r to zero
+    assert!(Fr::from_bytes(&R_BE).is_zero());
+}
+
+#[test]
+fn compile_time_field_constants() {
+    use ultrahonk_soroban_verifier::relations::{
+        INTERNAL_MATRIX_DIAGONAL, LIMB_SIZE, NEG_HALF, SUBLIMB_SHIFT,
+    };
+
+    const R_MINUS_ONE: Fr =
+        Fr::from_str_const("0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000");
+    assert_eq!(R_MINUS_ONE, -Fr::one());
+    for hex in ["0x0", "1", "0xabc", "0x2d0", "0x100000000000000000"] {
+        assert_eq!(Fr::from_str_const(hex), Fr::from_str(hex));
+    }
+    assert_eq!(Fr::from_u64(u64::MAX), Fr::from_str("0xffffffffffffffff"));
+
+    assert_eq!(NEG_HALF + NEG_HALF, -Fr::one());
+    assert_eq!(LIMB_SIZE, Fr::from_u64(2).pow(68));
+    assert_eq!(SUBLIMB_SHIFT, Fr::from_u64(2).pow(14));
+    let diagonal = [
+        "0x10dc6e9c006ea38b04b1e03b4bd9490c0d03f98929ca1d7fb56821fd19d3b6e7",
+        "0x0c28145b6a44df3e0149b3d0a30b3bb599df9756d4dd9b84a86b38cfb45a740b",
+        "0x00544b8338791518b2c7645a50392798b21f75bb60e3596170067d00141cac15",
+        "0x222c01175718386f2e2e82eb122789e352e105a3b8fa852613bc534433ee428b",
+    ];
+    for (c, hex) in INTERNAL_MATRIX_DIAGONAL.iter().zip(diagonal) {
+        assert_eq!(*c, Fr::from_str(hex));
+    }
+}
+
+fn check_flavor<F: Flavor>() {
+    assert_eq!(F::PRECOMPUTED.len() + F::WITNESS.len(), F::NUMBER_UNSHIFTED);
+    assert_eq!(F::SHIFTED.len(), F::NUMBER_TO_BE_SHIFTED);
+    assert_eq!(
+        F::NUMBER_UNSHIFTED + F::NUMBER_TO_BE_SHIFTED,
+        F::NUMBER_OF_ENTITIES
+    );
+    // Commitments line up with the sumcheck evaluations they open.
+    for (i, w) in F::PRECOMPUTED.iter().enumerate() {
+        assert_eq!(w.index(), i);
+    }
+    // Every VK layout carries each precomputed commitment exactly once.
+    for format in VkFormat::ALL {
+        for w in F::PRECOMPUTED {
+            let n = format
+                .commitments()
+                .iter()
+                .filter(|v| v.index() == w.index());
+            assert_eq!(n.count(), 1, "{format:?} {w:?}");
+        }
+    }
+    let witness = [
+        (WitnessCommitment::W1, Wire::Wl),
+        (WitnessCommitment::W2, Wire::Wr),
+        (WitnessCommitment::W3, Wire::Wo),
+        (WitnessCommitment::W4, Wire::W4),
+        (WitnessCommitment::ZPerm, Wire::ZPerm),
+        (WitnessCommitment::LookupInverses, Wire::LookupInverses),
+        (WitnessCommitment::LookupReadCounts, Wire::LookupReadCounts),
+        (WitnessCommitment::LookupReadTags, Wire::LookupReadTags),
+    ];
+    assert_eq!(F::WITNESS.len(), witness.len());
+    for (k, (c, w)) in witness.into_iter().enumerate() {
+        assert_eq!(F::WITNESS[k], c);
+        assert_eq!(w.index(), F::PRECOMPUTED.len() + k);
+    }
+
+    let bary = F::BARYCENTRIC;
+    let bary = bary.as_ref();
+    assert_eq!(bary.len(), F::BATCHED_RELATION_PARTIAL_LENGTH);
+    for (i, b) in bary.iter().enumerate() {
+        let mut expected = Fr::one();
+        for j in 0..bary.len() {
+            if j != i {
+                expected = expected * (Fr::from_u64(i as u64) - Fr::from_u64(j as u64));
+            }
+        }
+        assert_eq!(*b, expected);
+    }
+}
+
+#[test]
+fn ultra_flavors_are_consistent() {
+    check_flavor::<UltraFlavor>();
+    check_flavor::<UltraZkFlavor>();
+}
+
+#[cfg(feature = "fr32")]
+#[test]
+fn fr32_arithmetic_matches_ark() {
+    let mut r_minus_one = R_BE;
+    r_minus_one[31] -= 1;
+    let mut values = vec![
+        Fr::zero(),
+        Fr::one(),
+        Fr::from_u64(2),
+        Fr::from_u64(u64::MAX),
+        Fr::from_bytes(&r_minus_one),
+        Fr::from_bytes(&[0xff; 32]),
+        Fr::from_str("0x183227397098d014dc2822db40c0ac2e9419f4243cdcb848a1f0fac9f8000000"),
+    ];
+    // pseudo-random elements from keccak, independent of Fr arithmetic
+    let env = Env::default();
+    for i in 0..64u8 {
+        let h = env.crypto().keccak256(&Bytes::from_array(&env, &[i; 4]));
+        values.push(Fr::from_bytes(&h.to_array()));
+    }
+    for a in &values {
+        for b in &values {
+            assert_eq!((*a * *b).0, a.0 * b.0);
+            assert_eq!((*a + *b).0, a.0 + b.0);
+            assert_eq!((*a - *b).0, a.0 - b.0);
+        }
+        assert_eq!(a.pow(5).0, a.0 * a.0 * a.0 * a.0 * a.0);
+        assert_eq!(a.pow(0), Fr::one());
+    }
+}
+
+#[test]
+fn non_canonical_proof_encodings_are_rejected() -> Result<(), String> {
+    let path = Path::new("circuits/simple_circuit/target");
+    let env = Env::default();
+    env.ledger().set_protocol_version(25);
+
+    let proof_bytes = fs::read(path.join("proof")).map_err(|e| e.to_string())?;
+    let vk_bytes = fs::read(path.join("vk")).map_err(|e| e.to_string())?;
+    let public_inputs = fs::read(path.join("public_inputs")).map_err(|e| e.to_string())?;
+    let verifier = UltraHonkVerifier::new(&env, &Bytes::from_slice(&env, &vk_bytes))
+        .map_err(|e| format!("{e:?}"))?;
+    let verify = |proof: &[u8], pis: &[u8]| {
+        verifier.verify(
+            &Bytes::from_slice(&env, proof),
+            &Bytes::from_slice(&env, pis),
+        )
+    };
+
+    // pairing point limb + r
+    let mut proof = proof_bytes.clone();
+    add_scalar_modulus(&mut proof[..32]);
+    assert_eq!(
+        load_proof(&Bytes::from_slice(&env, &proof)).err(),
+        Some(ParseError::NonCanonicalScalar)
+    );
+    assert!(matches!(
+        verify(&proof, &public_inputs),
+        Err(VerifyError::Parse(ParseError::NonCanonicalScalar))
+    ));
+
+    // w1.x low limb with bits above 136
+    let mut proof = proof_bytes.clone();
+    proof[PAIRING_POINTS_SIZE * 32] = 1;
+    assert!(matches!(
+        verify(&proof, &public_inputs),
+        Err(VerifyError::Parse(ParseError::NonCanonicalCoordinate))
+    ));
+
+    // last sumcheck evaluation + r
+    let mut proof = proof_bytes.clone();
+    let off = PAIRING_POINTS_SIZE * 32
+        + 8 * 4 * 32
+        + CONST_PROOF_SIZE_LOG_N * BATCHED_RELATION_PARTIAL_LENGTH * 32
+        + (NUMBER_OF_ENTITIES - 1) * 32;
+    add_scalar_modulus(&mut proof[off..off + 32]);
+    assert!(matches!(
+        verify(&proof, &public_inputs),
+        Err(VerifyError::Parse(ParseError::NonCanonicalScalar))
+    ));
+
+    // public input + r
+    let mut pis = public_inputs.clone();
+    add_scalar_modulus(&mut pis[..32]);
+    assert!(matches!(
+        verify(&proof_bytes, &pis),
+        Err(VerifyError::Parse(ParseError::NonCanonicalScalar))
+    ));
+    Ok(())
+}
+
+#[test]
+fn malformed_inputs_return_parse_errors() -> Result<(), String> {
+    let path = Path::new("circuits/simple_circuit/target");
+    let env = Env::default();
+    env.ledger().set_protocol_version(25);
+    let proof_bytes = fs::read(path.join("proof")).map_err(|e| e.to_string())?;
+    let vk_bytes = fs::read(path.join("vk")).map_err(|e| e.to_string())?;
+    let load_vk = |vk: &[u8]| load_vk_from_bytes(&Bytes::from_slice(&env, vk)).err();
+
+    // lengths
+    assert_eq!(
+        load_proof(&Bytes::from_slice(&env, &proof_bytes[..PROOF_BYTES - 32])).err(),
+        Some(ParseError::WrongLength)
+    );
+    assert_eq!(
+        load_vk(&vk_bytes[..vk_bytes.len() - 1]),
+        Some(ParseError::WrongLength)
+    );
+    assert!(matches!(
+        UltraHonkVerifier::new(&env, &Bytes::from_slice(&env, &vk_bytes[1..])),
+        Err(VerifyError::Parse(ParseError::WrongLength))
+    ));
+
+    // VK points start after the 4×u64 header: qm, …, s1 is the 14th
+    let point = |i: usize| VkFormat::V0_87.header_bytes() + 64 * i;
+    let mut vk = vk_bytes.clone();
+    vk[point(0) + 63] ^= 1;
+    assert_eq!(load_vk(&vk), Some(ParseError::PointNotOnCurve));
+    let mut vk = vk_bytes.clone();
+    vk[point(13)..point(14)].fill(0);
+    assert_eq!(load_vk(&vk), Some(ParseError::PointAtInfinity));
+    let mut vk = vk_bytes.clone();
+    vk[point(0)..point(0) + 32].fill(0xff);
+    assert_eq!(load_vk(&vk), Some(ParseError::NonCanonicalCoordinate));
+
+    // w1 with a tweaked y low limb
+    let mut proof = proof_bytes.clone();
+    proof[PAIRING_POINTS_SIZE * 32 + 3 * 32 - 1] ^= 1;
+    assert_eq!(
+        load_proof(&Bytes::from_slice(&env, &proof)).err(),
+        Some(ParseError::PointNotOnCurve)
+    );
+
+    assert_eq!(
+        Fr::try_from_str("0xzz"),
+        Err(ParseError::NonCanonicalScalar)
+    );
+    assert_eq!(Fr::try_from_str("0x1"), Ok(Fr::one()));
+    Ok(())
+}
+
+#[test]
+fn vk_metadata_is_validated() -> Result<(), String> {
+    let env = Env::default();
+    let vk_bytes = fs::read("circuits/simple_circuit/target/vk").map_err(|e| e.to_string())?;
+    // V0_87 header: circuit_size, log_circuit_size, public_inputs_size, offset
+    let with_header = |circuit_size: u64, log_n: u64, pis: u64| {
+        let mut vk = vk_bytes.clone();
+        vk[0..8].copy_from_slice(&circuit_size.to_be_bytes());
+        vk[8..16].copy_from_slice(&log_n.to_be_bytes());
+        vk[16..24].copy_from_slice(&pis.to_be_bytes());
+        UltraHonkVerifier::new(&env, &Bytes::from_slice(&env, &vk)).err()
+    };
+    let invalid = |e| Some(VerifyError::InvalidVk(e));
+    let cases = [
+        (with_header(1, 0, 17), VkError::LogCircuitSizeZero),
+        (
+            with_header(1 << 29, 29, 17),
+            VkError::LogCircuitSizeTooLarge,
+        ),
+        (with_header(1 << 5, 6, 17), VkError::CircuitSizeMismatch),
+        (with_header(1 << 6, 6, 15), VkError::TooFewPublicInputs),
+    ];
+    for (got, want) in cases {
+        assert_eq!(got, invalid(want));
+    }
+    assert!(with_header(1 << 6, 6, 17).is_none());
+    Ok(())
+}
+
+/// The sample VK with its public inputs moved to trace row `offset`.
+fn vk_with_offset(vk: &[u8], offset: u64) -> Vec<u8> {
+    let mut out = vk.to_vec();
+    out[24..32].copy_from_slice(&offset.to_be_bytes());
+    out
+}
+
+#[test]
+fn vk_pub_inputs_offset_is_honoured() -> Result<(), String> {
+    let path = Path::new("circuits/simple_circuit/target");
+    let env = Env::default();
+    env.ledger().set_protocol_version(25);
+    let proof_bytes = fs::read(path.join("proof")).map_err(|e| e.to_string())?;
+    let vk_bytes = fs::read(path.join("vk")).map_err(|e| e.to_string())?;
+    let public_inputs = fs::read(path.join("public_inputs")).map_err(|e| e.to_string())?;
+    let offset_of =
+        |vk: &[u8]| load_vk_from_bytes(&Bytes::from_slice(&env, vk)).map(|vk| vk.pub_inputs_offset);
+
+    assert_eq!(offset_of(&vk_bytes), Ok(1));
+    let shifted = vk_with_offset(&vk_bytes, 2);
+    assert_eq!(offset_of(&shifted), Ok(2));
+
+    // bb v0.87.0 places the public inputs right after the single zero row,
+    // so every UltraHonk VK it writes has offset 1 and no proof for another
+    // offset can be produced with it. Check the offset against a direct
+    // evaluation of the permutation public-input delta instead:
+    // ∏ (x_i + γ + β·(n + offset + i)) / ∏ (x_i + γ - β·(offset + 1 + i))
+    // over the public inputs followed by the pairing point object.
+    let proof = Bytes::from_slice(&env, &proof_bytes);
+    let pis = Bytes::from_slice(&env, &public_inputs);
+    let inputs: Vec<Fr> = public_inputs
+        .chunks(32)
+        .map(|w| Fr::from_bytes(w.try_into().unwrap()))
+        .chain(
+            load_proof(&proof)
+                .map_err(|e| format!("{e:?}"))?
+                .pairing_point_object,
+        )
+        .collect();
+    let mut etas = Vec::new();
+    for (vk, offset) in [(&vk_bytes, 1u64), (&shifted, 2)] {
+        let verifier = UltraHonkVerifier::new(&env, &Bytes::from_slice(&env, vk))
+            .map_err(|e| format!("{e:?}"))?;
+        let n = verifier.get_vk().circuit_size;
+        let t = verifier
+            .stage_transcript(&proof, &pis)
+            .map_err(|e| format!("{e:?}"))?;
+        let (beta, gamma) = (t.rel_params.beta, t.rel_params.gamma);
+        let (mut num, mut den) = (Fr::one(), Fr::one());
+        for (i, x) in (0u64..).zip(&inputs) {
+            num = num * (*x + gamma + beta * Fr::from_u64(n + offset + i));
+            den = den * (*x + gamma - beta * Fr::from_u64(offset + 1 + i));
+        }
+        let expected = num * den.inverse().ok_or("zero denominator")?;
+        assert_eq!(
+            t.rel_params.public_inputs_delta, expected,
+            "offset {offset}"
+        );
+        etas.push(t.rel_params.eta);
+    }
+    // The offset is also absorbed into the transcript.
+    assert_ne!(etas[0], etas[1]);
+
+    // The proof commits to offset 1, so the shifted VK rejects it.
+    let verifier = UltraHonkVerifier::new(&env, &Bytes::from_slice(&env, &shifted))
+        .map_err(|e| format!("{e:?}"))?;
+    assert!(matches!(
+        verifier.verify(&proof, &pis),
+        Err(VerifyError::SumcheckFailed(_))
+    ));
+
+    // Public inputs past the end of the trace
+    let vk =
+        load_vk_from_bytes(&Bytes::from_slice(&env, &vk_bytes)).map_err(|e| format!("{e:?}"))?;
+    let overflowing = vk_with_offset(&vk_bytes, vk.circuit_size);
+    assert_eq!(
+        UltraHonkVerifier::new(&env, &Bytes::from_slice(&env, &overflowing)).err(),
+        Some(VerifyError::InvalidVk(VkError::PublicInputsOutOfRange))
+    );
+    Ok(())
+}
+
+#[test]
+fn staged_verification_matches_verify() -> Result<(), String> {
+    for dir in [
+        "circuits/simple_circuit/target",
+        "circuits/simple_circuit/target/zk",
+    ] {
+        let path = Path::new(dir);
+        let env = Env::default();
+        env.ledger().set_protocol_version(25);
+        let proof = Bytes::from_slice(
+            &env,
+            &fs::read(path.join("proof")).map_err(|e| e.to_string())?,
+        );
+        let vk = Bytes::from_slice(&env, &fs::read(path.join("vk")).map_err(|e| e.to_string())?);
+        let mut pis = fs::read(path.join("public_inputs")).map_err(|e| e.to_string())?;
+        let public_inputs = Bytes::from_slice(&env, &pis);
+        let verifier = UltraHonkVerifier::new(&env, &vk).map_err(|e| format!("{e:?}"))?;
+
+        // The transcript survives a storage round trip unchanged
+        let t = verifier
+            .stage_transcript(&proof, &public_inputs)
+            .map_err(|e| format!("{e:?}"))?;
+        let stored = transcript_to_bytes(&env, &t);
+        assert_eq!(stored.len() as usize, 32 * TRANSCRIPT_FIELDS);
+        let t = load_transcript(&stored).map_err(|e| format!("{e:?}"))?;
+        assert_eq!(transcript_to_bytes(&env, &t), stored);
+
+        verifier
+            .stage_sumcheck(&proof, &t)
+            .map_err(|e| format!("{e:?}"))?;
+        verifier
+            .stage_shplemini(&proof, &t)
+            .map_err(|e| format!("{e:?}"))?;
+
+        // A transcript from other public inputs does not carry over
+        pis[31] ^= 1;
+        let t = verifier
+            .stage_transcript(&proof, &Bytes::from_slice(&env, &pis))
+            .map_err(|e| format!("{e:?}"))?;
+        assert!(matches!(
+            verifier.stage_sumcheck(&proof, &t),
+            Err(VerifyError::SumcheckFailed(_))
+        ));
+    }
+    assert_eq!(
+        load_transcript(&Bytes::from_slice(&Env::default(), &[0u8; 32])).err(),
+        Some(ParseError::WrongLength)
+    );
+    Ok(())
+}
+
+#[test]
+fn grumpkin_group_law() {
+    let g = GrumpkinPoint::generator();
+    assert!(g.is_on_curve());
+    // the group order is the BN254 base field modulus: (q − 1)·G = −G
+    assert_eq!(g.scalar_mul(&-Fq::from(1u64)), -g);
+    assert!((g + -g).is_infinity());
+    assert_eq!(g + g, g.scalar_mul(&Fq::from(2u64)));
+
+    let points: Vec<GrumpkinPoint> = (1..=5u64).map(|k| g.scalar_mul(&Fq::from(k))).collect();
+    let scalars: Vec<Fq> = (0..5u64).map(|k| Fq::from(k * 1_000_003 + 7)).collect();
+    let expected = points
+        .iter()
+        .zip(&scalars)
+        .fold(GrumpkinPoint::infinity(), |acc, (p, s)| {
+            acc + p.scalar_mul(s)
+        });
+    assert_eq!(msm(&points, &scalars).unwrap(), expected);
+}
+
+/// Rollup verifier, proof and public inputs, plus the Grumpkin SRS (2^16
+/// points as 64-byte big-endian (x, y)) copied by `tests/build_circuits.sh`.
+#[allow(clippy::type_complexity)]
+fn rollup_fixture(
+    env: &Env,
+) -> Result<(UltraHonkVerifier, Vec<u8>, Bytes, Vec<GrumpkinPoint>), String> {
+    let path = Path::new("circuits/simple_circuit/target/rollup");
+    let srs = load_grumpkin_srs(&fs::read(path.join("grumpkin_srs")).map_err(|e| e.to_string())?)?;
+    let proof_bytes = fs::read(path.join("proof")).map_err(|e| e.to_string())?;
+    assert_eq!(proof_bytes.len(), ROLLUP_PROOF_BYTES);
+    let vk_bytes = fs::read(path.join("vk")).map_err(|e| e.to_string())?;
+    let public_inputs = fs::read(path.join("public_inputs")).map_err(|e| e.to_string())?;
+
+    let verifier = UltraHonkVerifier::new(env, &Bytes::from_slice(env, &vk_bytes))
+        .map_err(|e| format!("{e:?}"))?
+        .with_oracle_hash(OracleHash::Poseidon2);
+    Ok((
+        verifier,
+        proof_bytes,
+        Bytes::from_slice(env, &public_inputs),
+        srs,
+    ))
+}
+
+#[test]
+fn simple_circuit_rollup_proof_verifies() -> Result<(), String> {
+    let env = Env::default();
+    env.ledger().set_protocol_version(25);
+    let (verifier, proof_bytes, public_inputs, srs) = rollup_fixture(&env)?;
+    verifier
+        .verify_rollup(&Bytes::from_slice(&env, &proof_bytes), &public_inputs, &srs)
+        .map_err(|e| format!("{e:?}"))
+}
+
+#[test]
+fn tampered_ipa_proof_is_rejected() -> Result<(), String> {
+    let env = Env::default();
+    env.ledger().set_protocol_version(25);
+    let (verifier, mut proof_bytes, public_inputs, srs) = rollup_fixture(&env)?;
+    // Low byte of a_0's low limb, the second-to-last word: the honk part and
+    // G_0 still check out, the final opening equation does not.
+    let a_zero_lo = proof_bytes.len() - 33;
+    proof_bytes[a_zero_lo] ^= 1;
+    assert!(matches!(
+        verifier.verify_rollup(&Bytes::from_slice(&env, &proof_bytes), &public_inputs, &srs),
+        Err(VerifyError::IpaFailed(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn resetting_a_hash_input_costs_less_than_clear_and_extend() {
+    let env = Env::default();
+    let challenge = [7u8; 32];
+    let mut buf = Bytes::from_slice(&env, &[1u8; 1024]);
+    let mut budget = env.cost_estimate().budget();
+
+    budget.reset_unlimited();
+    for _ in 0..100 {
+        buf = Bytes::new(&env);
+        buf.extend_from_slice(&challenge);
+    }
+    let clear_and_extend = budget.cpu_instruction_cost();
+
+    budget.reset_unlimited();
+    for _ in 0..100 {
+        buf.reset(&challenge);
+    }
+    let reset = budget.cpu_instruction_cost();
+
+    assert_eq!(buf, Bytes::from_slice(&env, &challenge));
+    assert!(
+        reset < clear_and_extend,
+        "reset {reset} vs clear + extend {clear_and_extend}"
+    );
+}